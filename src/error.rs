@@ -0,0 +1,107 @@
+use std::{any::Any, io};
+
+use thiserror::Error;
+
+use crate::matrix::Dtype;
+
+#[derive(Debug, Error)]
+pub enum MatrixError {
+    #[error("matrix dimensions mismatch: {left:?} vs {right:?}")]
+    DimensionMismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    #[error("{len} elements do not fill a {rows}x{cols} matrix")]
+    InvalidShape {
+        len: usize,
+        rows: usize,
+        cols: usize,
+    },
+    #[error("row {row} has {found} elements, expected {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("expected a square matrix, got {rows}x{cols}")]
+    NotSquare { rows: usize, cols: usize },
+    #[error("cannot multiply an empty chain of matrices")]
+    EmptyChain,
+    #[error("matrix is singular (zero pivot in column {col})")]
+    Singular { col: usize },
+    #[error("invalid sparse matrix: {0}")]
+    InvalidSparse(&'static str),
+    #[error("parse error at line {line}, column {column}: {message}")]
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("csv error: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("{found} csv columns given for a matrix with {expected} columns")]
+    CsvColumns { expected: usize, found: usize },
+    #[error("invalid binary matrix: {0}")]
+    InvalidBinary(&'static str),
+    #[error("binary matrix holds {found:?} elements, expected {expected:?}")]
+    DtypeMismatch { expected: Dtype, found: Dtype },
+    #[error("i/o error: {0}")]
+    Io(#[from] io::Error),
+    #[error("vector length mismatch: {left} vs {right}")]
+    LengthMismatch { left: usize, right: usize },
+    #[error("arithmetic overflow at cell ({row}, {col})")]
+    Overflow { row: usize, col: usize },
+    #[error("worker failed at cell ({row}, {col}): {cause}")]
+    WorkerFailed {
+        row: usize,
+        col: usize,
+        cause: WorkerFailure,
+    },
+    #[error("executor shut down before the work was dispatched")]
+    Cancelled,
+    #[error("executor needs at least one worker thread")]
+    NoWorkers,
+    #[error("the global executor is already initialized")]
+    GlobalAlreadySet,
+    #[error("failed to spawn worker thread: {0}")]
+    Spawn(#[source] io::Error),
+}
+
+#[derive(Debug)]
+pub enum WorkerFailure {
+    /// The computation returned an error.
+    Error(Box<MatrixError>),
+    /// The computation panicked; holds the panic message.
+    Panic(String),
+    /// The worker went away before replying.
+    Disconnected,
+}
+
+impl MatrixError {
+    pub(crate) fn worker(row: usize, col: usize, cause: WorkerFailure) -> Self {
+        MatrixError::WorkerFailed { row, col, cause }
+    }
+}
+
+impl WorkerFailure {
+    pub(crate) fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .unwrap_or_else(|| "unknown panic".to_string()),
+        };
+        WorkerFailure::Panic(message)
+    }
+}
+
+impl std::fmt::Display for WorkerFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkerFailure::Error(e) => write!(f, "{}", e),
+            WorkerFailure::Panic(message) => write!(f, "panicked: {}", message),
+            WorkerFailure::Disconnected => write!(f, "worker disconnected"),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, OnceLock,
+    },
+    thread,
+};
+
+use crate::error::MatrixError;
+
+const NUM_THREADS: usize = 4;
+const SEQUENTIAL_THRESHOLD: usize = 0;
+const STRASSEN_CROSSOVER: usize = 128;
+/// Messages per worker thread targeted by `Granularity::Auto`.
+const MESSAGES_PER_THREAD: usize = 4;
+
+static GLOBAL: OnceLock<MatrixExecutor> = OnceLock::new();
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A pool of long-lived worker threads that matrix operations dispatch their
+/// messages to. Workers exit once the executor is dropped.
+pub struct MatrixExecutor {
+    senders: Vec<mpsc::Sender<Job>>,
+    handles: Vec<thread::JoinHandle<()>>,
+    next: AtomicUsize,
+    sequential_threshold: usize,
+    granularity: Granularity,
+    strassen_crossover: usize,
+}
+
+/// How much of the output a single worker message computes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Granularity {
+    /// Blocks of whole output rows, sized so each worker thread receives a
+    /// few messages per operation.
+    #[default]
+    Auto,
+    /// One output cell per message.
+    Cell,
+    /// Blocks of whole output rows.
+    Rows(usize),
+    /// Tiles of `rows x cols` output cells.
+    Tile(usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct MatrixExecutorBuilder {
+    num_threads: Option<usize>,
+    sequential_threshold: usize,
+    granularity: Granularity,
+    strassen_crossover: usize,
+}
+
+impl Default for MatrixExecutorBuilder {
+    fn default() -> Self {
+        Self {
+            num_threads: None,
+            sequential_threshold: SEQUENTIAL_THRESHOLD,
+            granularity: Granularity::default(),
+            strassen_crossover: STRASSEN_CROSSOVER,
+        }
+    }
+}
+
+impl MatrixExecutorBuilder {
+    /// Number of worker threads; defaults to the available parallelism.
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    /// Operations needing at most this many multiply-adds (or element
+    /// updates, for elementwise operations) are computed on the calling
+    /// thread instead of being sent to the workers.
+    pub fn sequential_threshold(mut self, threshold: usize) -> Self {
+        self.sequential_threshold = threshold;
+        self
+    }
+
+    pub fn granularity(mut self, granularity: Granularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    /// Square products larger than this are split recursively with
+    /// Strassen's algorithm by `multiply_strassen`.
+    pub fn strassen_crossover(mut self, n: usize) -> Self {
+        self.strassen_crossover = n;
+        self
+    }
+
+    pub fn build(self) -> Result<MatrixExecutor, MatrixError> {
+        let num_threads = self.num_threads.unwrap_or_else(|| {
+            thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(NUM_THREADS)
+        });
+        if num_threads == 0 {
+            return Err(MatrixError::NoWorkers);
+        }
+        let mut senders = Vec::with_capacity(num_threads);
+        let mut handles = Vec::with_capacity(num_threads);
+        for idx in 0..num_threads {
+            let (tx, rx) = mpsc::channel::<Job>();
+            let handle = thread::Builder::new()
+                .name(format!("matrix-worker-{}", idx))
+                .spawn(move || {
+                    for job in rx {
+                        job();
+                    }
+                })
+                .map_err(MatrixError::Spawn)?;
+            senders.push(tx);
+            handles.push(handle);
+        }
+        Ok(MatrixExecutor {
+            senders,
+            handles,
+            next: AtomicUsize::new(0),
+            sequential_threshold: self.sequential_threshold,
+            granularity: self.granularity,
+            strassen_crossover: self.strassen_crossover,
+        })
+    }
+}
+
+impl MatrixExecutor {
+    pub fn new(num_threads: usize) -> Result<Self, MatrixError> {
+        Self::builder().num_threads(num_threads).build()
+    }
+
+    pub fn builder() -> MatrixExecutorBuilder {
+        MatrixExecutorBuilder::default()
+    }
+
+    /// The process-wide executor used by `multiply` and the operators.
+    pub fn global() -> &'static MatrixExecutor {
+        GLOBAL.get_or_init(|| {
+            MatrixExecutor::builder()
+                .build()
+                .expect("Failed to start matrix workers")
+        })
+    }
+
+    /// Configures the process-wide executor. Only succeeds before the
+    /// global executor is first used.
+    pub fn set_global(builder: MatrixExecutorBuilder) -> Result<(), MatrixError> {
+        GLOBAL
+            .set(builder.build()?)
+            .map_err(|_| MatrixError::GlobalAlreadySet)
+    }
+
+    pub fn num_threads(&self) -> usize {
+        self.senders.len()
+    }
+
+    pub fn sequential_threshold(&self) -> usize {
+        self.sequential_threshold
+    }
+
+    pub fn granularity(&self) -> Granularity {
+        self.granularity
+    }
+
+    pub fn strassen_crossover(&self) -> usize {
+        self.strassen_crossover
+    }
+
+    /// Items per message for `Granularity::Auto`, out of `len`.
+    pub(crate) fn auto_chunk(&self, len: usize) -> usize {
+        len.div_ceil(self.num_threads() * MESSAGES_PER_THREAD)
+            .max(1)
+    }
+
+    pub(crate) fn execute(&self, job: impl FnOnce() + Send + 'static) -> Result<(), MatrixError> {
+        let idx = self.next.fetch_add(1, Ordering::Relaxed) % self.senders.len();
+        self.senders[idx]
+            .send(Box::new(job))
+            .map_err(|_| MatrixError::Cancelled)
+    }
+}
+
+impl Drop for MatrixExecutor {
+    fn drop(&mut self) {
+        // closing the channels ends each worker's receive loop
+        self.senders.clear();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[test]
+fn test_executor_runs_jobs_and_joins_on_drop() -> anyhow::Result<()> {
+    let executor = MatrixExecutor::new(2)?;
+    let counter = std::sync::Arc::new(AtomicUsize::new(0));
+    for _ in 0..10 {
+        let counter = counter.clone();
+        executor.execute(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        })?;
+    }
+    drop(executor);
+    assert_eq!(counter.load(Ordering::Relaxed), 10);
+    Ok(())
+}
+
+#[test]
+fn test_executor_rejects_zero_threads() {
+    assert!(matches!(
+        MatrixExecutor::new(0),
+        Err(MatrixError::NoWorkers)
+    ));
+}
+
+#[test]
+fn test_executor_builder_options() -> anyhow::Result<()> {
+    let executor = MatrixExecutor::builder()
+        .num_threads(3)
+        .sequential_threshold(64)
+        .build()?;
+    assert_eq!(executor.num_threads(), 3);
+    assert_eq!(executor.sequential_threshold(), 64);
+
+    let executor = MatrixExecutor::builder().build()?;
+    assert!(executor.num_threads() >= 1);
+    assert_eq!(executor.granularity(), Granularity::Auto);
+    assert_eq!(
+        executor.auto_chunk(100),
+        100usize.div_ceil(executor.num_threads() * 4)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_set_global_only_before_first_use() {
+    // other tests may already have initialized it
+    let _ = MatrixExecutor::set_global(MatrixExecutor::builder());
+    assert!(matches!(
+        MatrixExecutor::set_global(MatrixExecutor::builder()),
+        Err(MatrixError::GlobalAlreadySet)
+    ));
+    assert!(MatrixExecutor::global().num_threads() >= 1);
+}
@@ -1,6 +1,13 @@
+mod error;
+mod executor;
 mod matrix;
 mod metrics;
+mod semiring;
 mod vector;
 
+pub use error::*;
+pub use executor::*;
 pub use matrix::*;
 pub use metrics::*;
+pub use semiring::*;
+pub use vector::*;
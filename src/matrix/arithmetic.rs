@@ -0,0 +1,428 @@
+use std::{
+    borrow::Cow,
+    ops::{Add, Mul, Neg, Sub},
+    sync::Arc,
+};
+
+use super::{multiply, packed, Layout, Matrix, MatrixExecutor, ELEMENTWISE_CHUNK};
+use crate::{error::MatrixError, semiring::Semiring};
+
+pub fn add<T>(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+where
+    T: Copy + Add<Output = T> + Send + Sync + 'static,
+{
+    MatrixExecutor::global().add(a, b)
+}
+
+pub fn subtract<T>(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+where
+    T: Copy + Sub<Output = T> + Send + Sync + 'static,
+{
+    MatrixExecutor::global().subtract(a, b)
+}
+
+/// Elementwise product of two matrices of the same shape.
+pub fn hadamard<T>(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+where
+    T: Copy + Mul<Output = T> + Send + Sync + 'static,
+{
+    MatrixExecutor::global().hadamard(a, b)
+}
+
+pub fn scale<T>(a: &Matrix<T>, k: T) -> Result<Matrix<T>, MatrixError>
+where
+    T: Copy + Mul<Output = T> + Send + Sync + 'static,
+{
+    MatrixExecutor::global().scale(a, k)
+}
+
+pub fn negate<T>(a: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+where
+    T: Copy + Neg<Output = T> + Send + Sync + 'static,
+{
+    MatrixExecutor::global().negate(a)
+}
+
+pub fn transpose<T>(a: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+where
+    T: Copy + Send + Sync + 'static,
+{
+    MatrixExecutor::global().transpose(a)
+}
+
+impl MatrixExecutor {
+    pub fn add<T>(&self, a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Copy + Add<Output = T> + Send + Sync + 'static,
+    {
+        self.zip_elements(a, b, |x, y| x + y)
+    }
+
+    pub fn subtract<T>(&self, a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Copy + Sub<Output = T> + Send + Sync + 'static,
+    {
+        self.zip_elements(a, b, |x, y| x - y)
+    }
+
+    pub fn hadamard<T>(&self, a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Copy + Mul<Output = T> + Send + Sync + 'static,
+    {
+        self.zip_elements(a, b, |x, y| x * y)
+    }
+
+    pub fn scale<T>(&self, a: &Matrix<T>, k: T) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Copy + Mul<Output = T> + Send + Sync + 'static,
+    {
+        self.map_elements(a, move |x| x * k)
+    }
+
+    pub fn negate<T>(&self, a: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Copy + Neg<Output = T> + Send + Sync + 'static,
+    {
+        self.map_elements(a, |x| -x)
+    }
+
+    pub fn transpose<T>(&self, a: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+    {
+        if self.runs_locally(a.data.len()) {
+            return Ok(transpose_local(a));
+        }
+        // copied once for the workers, as `multiply` does
+        self.transpose_shared(&a.to_shared())
+    }
+
+    /// Like `transpose`, but hands the workers the caller's matrix instead
+    /// of copying it.
+    pub fn transpose_shared<T>(&self, a: &Arc<Matrix<T>>) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+    {
+        if self.runs_locally(a.data.len()) {
+            return Ok(transpose_local(a));
+        }
+        let (row, col) = a.shape();
+        let a = a.clone();
+        let data = self.map_chunked(row * col, row, ELEMENTWISE_CHUNK, move |idx| {
+            Ok(a[(idx % row, idx / row)])
+        })?;
+        Ok(Matrix {
+            data,
+            row: col,
+            col: row,
+            layout: Layout::RowMajor,
+        })
+    }
+
+    /// Combines corresponding elements of two matrices of the same shape,
+    /// handing the workers the caller's operands instead of copying them.
+    /// The result keeps `a`'s layout.
+    pub fn zip_shared<T, U, V, F>(
+        &self,
+        a: &Arc<Matrix<T>>,
+        b: &Arc<Matrix<U>>,
+        op: F,
+    ) -> Result<Matrix<V>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Copy + Send + Sync + 'static,
+        V: Send + 'static,
+        F: Fn(T, U) -> V + Send + Sync + 'static,
+    {
+        check_same_shape(a, b)?;
+        if self.runs_locally(a.data.len()) {
+            return Ok(zip_local(a, b, op));
+        }
+        let (row, col, layout) = (a.row, a.col, a.layout);
+        // elementwise by storage index, so both sides need the same layout
+        let (a, b) = (a.clone(), packed(b.clone(), layout));
+        let data = self.map_chunked(a.data.len(), col, ELEMENTWISE_CHUNK, move |idx| {
+            Ok(op(a.data[idx], b.data[idx]))
+        })?;
+        Ok(Matrix {
+            data,
+            row,
+            col,
+            layout,
+        })
+    }
+
+    /// Applies `op` to every element, handing the workers the caller's
+    /// matrix instead of copying it. The result keeps `a`'s layout.
+    pub fn map_shared<T, U, F>(&self, a: &Arc<Matrix<T>>, op: F) -> Result<Matrix<U>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Send + 'static,
+        F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        if self.runs_locally(a.data.len()) {
+            return Ok(map_local(a, op));
+        }
+        let (row, col, layout) = (a.row, a.col, a.layout);
+        let a = a.clone();
+        let data = self.map_chunked(a.data.len(), col, ELEMENTWISE_CHUNK, move |idx| {
+            Ok(op(a.data[idx]))
+        })?;
+        Ok(Matrix {
+            data,
+            row,
+            col,
+            layout,
+        })
+    }
+
+    /// Small inputs are computed on in place, larger ones copied once for
+    /// the workers.
+    pub(crate) fn zip_elements<T, U, V, F>(
+        &self,
+        a: &Matrix<T>,
+        b: &Matrix<U>,
+        op: F,
+    ) -> Result<Matrix<V>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Copy + Send + Sync + 'static,
+        V: Send + 'static,
+        F: Fn(T, U) -> V + Send + Sync + 'static,
+    {
+        check_same_shape(a, b)?;
+        if self.runs_locally(a.data.len()) {
+            return Ok(zip_local(a, b, op));
+        }
+        self.zip_shared(&a.to_shared(), &Arc::new(b.to_layout(a.layout)), op)
+    }
+
+    pub(crate) fn map_elements<T, U, F>(
+        &self,
+        a: &Matrix<T>,
+        op: F,
+    ) -> Result<Matrix<U>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Send + 'static,
+        F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        if self.runs_locally(a.data.len()) {
+            return Ok(map_local(a, op));
+        }
+        self.map_shared(&a.to_shared(), op)
+    }
+}
+
+fn zip_local<T, U, V>(a: &Matrix<T>, b: &Matrix<U>, op: impl Fn(T, U) -> V) -> Matrix<V>
+where
+    T: Copy,
+    U: Copy,
+{
+    let b = if b.layout == a.layout {
+        Cow::Borrowed(b)
+    } else {
+        Cow::Owned(b.to_layout(a.layout))
+    };
+    Matrix {
+        data: a
+            .data
+            .iter()
+            .zip(&b.data)
+            .map(|(&x, &y)| op(x, y))
+            .collect(),
+        row: a.row,
+        col: a.col,
+        layout: a.layout,
+    }
+}
+
+fn map_local<T: Copy, U>(a: &Matrix<T>, op: impl Fn(T) -> U) -> Matrix<U> {
+    Matrix {
+        data: a.data.iter().map(|&x| op(x)).collect(),
+        row: a.row,
+        col: a.col,
+        layout: a.layout,
+    }
+}
+
+fn transpose_local<T: Copy>(a: &Matrix<T>) -> Matrix<T> {
+    Matrix::from_fn(a.col, a.row, |i, j| a[(j, i)])
+}
+
+fn check_same_shape<T, U>(a: &Matrix<T>, b: &Matrix<U>) -> Result<(), MatrixError> {
+    if a.shape() != b.shape() {
+        return Err(MatrixError::DimensionMismatch {
+            left: a.shape(),
+            right: b.shape(),
+        });
+    }
+    Ok(())
+}
+
+impl<T> Matrix<T>
+where
+    T: Copy + Send + Sync + 'static,
+{
+    pub fn transpose(&self) -> Matrix<T> {
+        transpose(self).expect("Matrix transpose error")
+    }
+}
+
+impl<T> Add for Matrix<T>
+where
+    T: Copy + Add<Output = T> + Send + Sync + 'static,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        MatrixExecutor::global()
+            .zip_shared(&Arc::new(self), &Arc::new(rhs), |x, y| x + y)
+            .expect("Matrix add error")
+    }
+}
+
+impl<T> Add for &Matrix<T>
+where
+    T: Copy + Add<Output = T> + Send + Sync + 'static,
+{
+    type Output = Matrix<T>;
+    fn add(self, rhs: Self) -> Self::Output {
+        add(self, rhs).expect("Matrix add error")
+    }
+}
+
+impl<T> Sub for Matrix<T>
+where
+    T: Copy + Sub<Output = T> + Send + Sync + 'static,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        MatrixExecutor::global()
+            .zip_shared(&Arc::new(self), &Arc::new(rhs), |x, y| x - y)
+            .expect("Matrix subtract error")
+    }
+}
+
+impl<T> Sub for &Matrix<T>
+where
+    T: Copy + Sub<Output = T> + Send + Sync + 'static,
+{
+    type Output = Matrix<T>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        subtract(self, rhs).expect("Matrix subtract error")
+    }
+}
+
+impl<T> Neg for Matrix<T>
+where
+    T: Copy + Neg<Output = T> + Send + Sync + 'static,
+{
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        MatrixExecutor::global()
+            .map_shared(&Arc::new(self), |x| -x)
+            .expect("Matrix negate error")
+    }
+}
+
+impl<T> Neg for &Matrix<T>
+where
+    T: Copy + Neg<Output = T> + Send + Sync + 'static,
+{
+    type Output = Matrix<T>;
+    fn neg(self) -> Self::Output {
+        negate(self).expect("Matrix negate error")
+    }
+}
+
+impl<T> Mul for &Matrix<T>
+where
+    T: Semiring,
+{
+    type Output = Matrix<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        multiply(self, rhs).expect("Matrix multiply error")
+    }
+}
+
+impl<T> Mul<T> for Matrix<T>
+where
+    T: Copy + Mul<Output = T> + Send + Sync + 'static,
+{
+    type Output = Self;
+    fn mul(self, k: T) -> Self::Output {
+        MatrixExecutor::global()
+            .map_shared(&Arc::new(self), move |x| x * k)
+            .expect("Matrix scale error")
+    }
+}
+
+impl<T> Mul<T> for &Matrix<T>
+where
+    T: Copy + Mul<Output = T> + Send + Sync + 'static,
+{
+    type Output = Matrix<T>;
+    fn mul(self, k: T) -> Self::Output {
+        scale(self, k).expect("Matrix scale error")
+    }
+}
+
+#[test]
+fn test_elementwise_arithmetic() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1, 2], [3, 4]])?;
+    let b = Matrix::from_rows([[5, 6], [7, 8]])?;
+    assert_eq!(&a + &b, Matrix::from_rows([[6, 8], [10, 12]])?);
+    assert_eq!(&b - &a, Matrix::from_rows([[4, 4], [4, 4]])?);
+    assert_eq!(-&a, Matrix::from_rows([[-1, -2], [-3, -4]])?);
+    assert_eq!(&a * 3, Matrix::from_rows([[3, 6], [9, 12]])?);
+    assert_eq!(hadamard(&a, &b)?, Matrix::from_rows([[5, 12], [21, 32]])?);
+    assert_eq!(&a * &b, Matrix::from_rows([[19, 22], [43, 50]])?);
+    assert_eq!(a.clone() + b.clone() - a.clone(), b);
+    assert!(matches!(
+        add(&a, &Matrix::zeros(2, 3)),
+        Err(MatrixError::DimensionMismatch { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_transpose_on_workers() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1, 2, 3], [4, 5, 6]])?;
+    let executor = MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(crate::Granularity::Rows(1))
+        .build()?;
+    let t = executor.transpose(&a)?;
+    assert_eq!(t, Matrix::from_rows([[1, 4], [2, 5], [3, 6]])?);
+    assert_eq!(t.transpose(), a);
+    assert_eq!(
+        executor.add(&a, &a)?,
+        Matrix::from_rows([[2, 4, 6], [8, 10, 12]])?
+    );
+
+    let on_worker = |_| {
+        std::thread::current()
+            .name()
+            .is_some_and(|name| name.starts_with("matrix-worker"))
+    };
+    assert!(!executor.map_elements(&a, on_worker)?.as_slice()[0]);
+
+    // large enough to be sent to the workers
+    let big = Matrix::from_fn(150, 200, |i, j| (i * 200 + j) as i64);
+    let shared = Arc::new(big.clone());
+    assert!(executor
+        .map_shared(&shared, on_worker)?
+        .as_slice()
+        .iter()
+        .all(|&w| w));
+    assert_eq!(
+        executor.transpose(&big)?,
+        Matrix::from_fn(200, 150, |i, j| (j * 200 + i) as i64)
+    );
+    let col_major = big.to_layout(crate::Layout::ColMajor);
+    assert_eq!(
+        executor.subtract(&big, &col_major)?,
+        Matrix::zeros(150, 200)
+    );
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+};
+
+use super::{check_multiply, multiply_sequential, AsMatrixView, Layout, Matrix, MatrixExecutor};
+use crate::{
+    error::{MatrixError, WorkerFailure},
+    semiring::Semiring,
+};
+
+/// Multiplies every `(a, b)` pair and returns the products in order.
+pub fn multiply_batch<T, A, B>(pairs: &[(A, B)]) -> Result<Vec<Matrix<T>>, MatrixError>
+where
+    T: Semiring,
+    A: AsMatrixView<T>,
+    B: AsMatrixView<T>,
+{
+    MatrixExecutor::global().multiply_batch(pairs)
+}
+
+/// Like `multiply_batch`, but yields each product as soon as it is done.
+pub fn multiply_batch_stream<T, A, B>(pairs: &[(A, B)]) -> Result<BatchStream<T>, MatrixError>
+where
+    T: Semiring,
+    A: AsMatrixView<T>,
+    B: AsMatrixView<T>,
+{
+    MatrixExecutor::global().multiply_batch_stream(pairs)
+}
+
+/// Products of a batch in completion order, as `(pair index, product)`.
+/// Ends once every pair has been yielded.
+#[derive(Debug)]
+pub struct BatchStream<T> {
+    receiver: mpsc::Receiver<(usize, Result<Matrix<T>, MatrixError>)>,
+    remaining: usize,
+}
+
+impl<T> Iterator for BatchStream<T> {
+    type Item = (usize, Result<Matrix<T>, MatrixError>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.receiver.recv().ok()?;
+        self.remaining -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for BatchStream<T> {}
+
+impl MatrixExecutor {
+    /// Each pair is computed whole by one worker, with pairs grouped into
+    /// messages by the executor's granularity. Errors report the pair index
+    /// as the row of the failing cell.
+    pub fn multiply_batch<T, A, B>(&self, pairs: &[(A, B)]) -> Result<Vec<Matrix<T>>, MatrixError>
+    where
+        T: Semiring,
+        A: AsMatrixView<T>,
+        B: AsMatrixView<T>,
+    {
+        let (pairs, work) = pack_pairs(pairs)?;
+        self.map_owned(pairs, work, |(a, b)| {
+            multiply_sequential(a.view(), b.view())
+        })
+    }
+
+    /// Sends one message per pair to the workers and returns without
+    /// waiting for any of them.
+    pub fn multiply_batch_stream<T, A, B>(
+        &self,
+        pairs: &[(A, B)],
+    ) -> Result<BatchStream<T>, MatrixError>
+    where
+        T: Semiring,
+        A: AsMatrixView<T>,
+        B: AsMatrixView<T>,
+    {
+        let (pairs, _) = pack_pairs(pairs)?;
+        let (tx, rx) = mpsc::channel();
+        let remaining = pairs.len();
+        for (idx, (a, b)) in pairs.into_iter().enumerate() {
+            let tx = tx.clone();
+            self.execute(move || {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    multiply_sequential(a.view(), b.view())
+                }))
+                .map_err(|payload| MatrixError::worker(idx, 0, WorkerFailure::from_panic(payload)))
+                .and_then(|product| {
+                    product
+                        .map_err(|e| MatrixError::worker(idx, 0, WorkerFailure::Error(Box::new(e))))
+                });
+                let _ = tx.send((idx, result));
+            })?;
+        }
+        Ok(BatchStream {
+            receiver: rx,
+            remaining,
+        })
+    }
+}
+
+type Pair<T> = (Matrix<T>, Matrix<T>);
+
+/// Checks every pair and copies it into the layouts the dot products read
+/// fastest. Also returns the batch's total multiply-adds.
+fn pack_pairs<T, A, B>(pairs: &[(A, B)]) -> Result<(Vec<Pair<T>>, usize), MatrixError>
+where
+    T: Clone,
+    A: AsMatrixView<T>,
+    B: AsMatrixView<T>,
+{
+    let mut work = 0;
+    let mut packed = Vec::with_capacity(pairs.len());
+    for (a, b) in pairs {
+        let (a, b) = (a.as_view(), b.as_view());
+        check_multiply(a.shape(), b.shape())?;
+        work += a.rows() * a.cols() * b.cols();
+        packed.push((a.to_layout(Layout::RowMajor), b.to_layout(Layout::ColMajor)));
+    }
+    Ok((packed, work))
+}
+
+#[test]
+fn test_multiply_batch_keeps_order() -> anyhow::Result<()> {
+    let executor = MatrixExecutor::builder()
+        .num_threads(3)
+        .granularity(crate::Granularity::Rows(2))
+        .build()?;
+    let pairs = (0..7)
+        .map(|n| {
+            let a = Matrix::from_fn(n + 1, 3, |i, j| (i + j * n) as i64);
+            let b = Matrix::from_fn(3, 2, |i, j| i as i64 - j as i64);
+            (a, b)
+        })
+        .collect::<Vec<_>>();
+    let expected = pairs
+        .iter()
+        .map(|(a, b)| super::multiply(a, b))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(executor.multiply_batch(&pairs)?, expected);
+
+    let mut streamed = executor.multiply_batch_stream(&pairs)?.collect::<Vec<_>>();
+    assert_eq!(streamed.len(), pairs.len());
+    streamed.sort_by_key(|(idx, _)| *idx);
+    for ((_, product), expected) in streamed.into_iter().zip(&expected) {
+        assert_eq!(&product?, expected);
+    }
+
+    let (a, b) = &pairs[0];
+    assert_eq!(
+        multiply_batch(&[(a, b), (a, b)])?,
+        vec![expected[0].clone(); 2]
+    );
+    assert!(matches!(
+        multiply_batch(&[(a, b), (a, a)]),
+        Err(MatrixError::DimensionMismatch { .. })
+    ));
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use std::io::{Read, Write};
+
+use super::{Layout, Matrix};
+use crate::error::MatrixError;
+
+const MAGIC: &[u8; 4] = b"MTRX";
+/// Elements converted per buffered read or write.
+const CHUNK: usize = 8192;
+
+/// Element type tag stored in the binary header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Dtype {
+    I8 = 1,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+}
+
+impl Dtype {
+    fn from_code(code: u8) -> Option<Self> {
+        use Dtype::*;
+        [I8, I16, I32, I64, U8, U16, U32, U64, F32, F64]
+            .into_iter()
+            .find(|dtype| *dtype as u8 == code)
+    }
+}
+
+/// Element types with a fixed-size little-endian encoding.
+pub trait BinaryElement: Copy {
+    const DTYPE: Dtype;
+    const SIZE: usize;
+    fn write_le(self, out: &mut Vec<u8>);
+    /// Decodes `bytes`, which hold exactly `SIZE` bytes.
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_binary_element {
+    ($($t:ty => $dtype:ident),*) => {$(
+        impl BinaryElement for $t {
+            const DTYPE: Dtype = Dtype::$dtype;
+            const SIZE: usize = size_of::<$t>();
+
+            fn write_le(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().expect("element size"))
+            }
+        }
+    )*};
+}
+
+impl_binary_element!(
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64,
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64,
+    f32 => F32, f64 => F64
+);
+
+impl<T: BinaryElement> Matrix<T> {
+    /// Writes the matrix as the magic `MTRX`, a dtype byte, `rows` and `cols`
+    /// as little-endian `u64`, then the row-major elements, little-endian.
+    pub fn write_binary(&self, mut writer: impl Write) -> Result<(), MatrixError> {
+        let mut buf = Vec::with_capacity(CHUNK * T::SIZE);
+        buf.extend_from_slice(MAGIC);
+        buf.push(T::DTYPE as u8);
+        buf.extend_from_slice(&(self.row as u64).to_le_bytes());
+        buf.extend_from_slice(&(self.col as u64).to_le_bytes());
+        writer.write_all(&buf)?;
+        let mut values = self.view().iter();
+        while values.len() > 0 {
+            buf.clear();
+            for &value in values.by_ref().take(CHUNK) {
+                value.write_le(&mut buf);
+            }
+            writer.write_all(&buf)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a matrix written by `write_binary`, which must hold elements
+    /// of type `T`.
+    pub fn read_binary(mut reader: impl Read) -> Result<Self, MatrixError> {
+        let mut header = [0u8; 21];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(MatrixError::InvalidBinary("bad magic"));
+        }
+        let found =
+            Dtype::from_code(header[4]).ok_or(MatrixError::InvalidBinary("unknown dtype"))?;
+        if found != T::DTYPE {
+            return Err(MatrixError::DtypeMismatch {
+                expected: T::DTYPE,
+                found,
+            });
+        }
+        let dim = |bytes: &[u8]| {
+            let n = u64::from_le_bytes(bytes.try_into().expect("8 header bytes"));
+            usize::try_from(n).map_err(|_| MatrixError::InvalidBinary("shape too large"))
+        };
+        let (row, col) = (dim(&header[5..13])?, dim(&header[13..21])?);
+        let len = row
+            .checked_mul(col)
+            .ok_or(MatrixError::InvalidBinary("shape too large"))?;
+
+        let mut data = Vec::new();
+        let mut buf = vec![0u8; CHUNK * T::SIZE];
+        while data.len() < len {
+            let n = (len - data.len()).min(CHUNK);
+            let bytes = &mut buf[..n * T::SIZE];
+            reader.read_exact(bytes)?;
+            data.extend(bytes.chunks_exact(T::SIZE).map(T::read_le));
+        }
+        Ok(Matrix {
+            data,
+            row,
+            col,
+            layout: Layout::RowMajor,
+        })
+    }
+}
+
+#[test]
+fn test_binary_round_trip() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1.5f64, -2.0, 3.0], [0.0, 4.25, -6.5]])?;
+    let mut bytes = Vec::new();
+    a.write_binary(&mut bytes)?;
+    assert_eq!(bytes.len(), 21 + 6 * 8);
+    assert_eq!(&bytes[..5], b"MTRX\x0a");
+    assert_eq!(Matrix::<f64>::read_binary(&bytes[..])?, a);
+
+    assert!(matches!(
+        Matrix::<i32>::read_binary(&bytes[..]),
+        Err(MatrixError::DtypeMismatch {
+            expected: Dtype::I32,
+            found: Dtype::F64
+        })
+    ));
+    assert!(matches!(
+        Matrix::<f64>::read_binary(&bytes[..bytes.len() - 1]),
+        Err(MatrixError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof
+    ));
+    Ok(())
+}
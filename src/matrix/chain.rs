@@ -0,0 +1,154 @@
+use super::{check_multiply, Matrix, MatrixExecutor};
+use crate::{error::MatrixError, semiring::Semiring};
+
+/// Multiplies `matrices` left to right in the cheapest order.
+pub fn multiply_chain<T>(matrices: &[&Matrix<T>]) -> Result<Matrix<T>, MatrixError>
+where
+    T: Semiring,
+{
+    MatrixExecutor::global().multiply_chain(matrices)
+}
+
+impl MatrixExecutor {
+    /// `a` raised to the `n`th power by repeated squaring; `a^0` is the
+    /// identity.
+    pub fn pow<T>(&self, a: &Matrix<T>, mut n: u32) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        if a.row != a.col {
+            return Err(MatrixError::NotSquare {
+                rows: a.row,
+                cols: a.col,
+            });
+        }
+        let mut result: Option<Matrix<T>> = None;
+        let mut base = a.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                result = Some(match result {
+                    Some(result) => self.multiply(&result, &base)?,
+                    None => base.clone(),
+                });
+            }
+            n >>= 1;
+            if n > 0 {
+                base = self.multiply(&base, &base)?;
+            }
+        }
+        Ok(result.unwrap_or_else(|| Matrix::identity(a.row)))
+    }
+
+    /// Picks the parenthesization needing the fewest scalar multiplications,
+    /// then computes each product with `multiply`.
+    pub fn multiply_chain<T>(&self, matrices: &[&Matrix<T>]) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        if matrices.is_empty() {
+            return Err(MatrixError::EmptyChain);
+        }
+        for pair in matrices.windows(2) {
+            check_multiply(pair[0].shape(), pair[1].shape())?;
+        }
+        let mut dims = vec![matrices[0].row];
+        dims.extend(matrices.iter().map(|m| m.col));
+        let (_, split) = chain_order(&dims);
+        self.multiply_range(matrices, &split, 0, matrices.len() - 1)
+    }
+
+    /// The product of `matrices[i..=j]`, split where `split[i][j]` says.
+    fn multiply_range<T>(
+        &self,
+        matrices: &[&Matrix<T>],
+        split: &[Vec<usize>],
+        i: usize,
+        j: usize,
+    ) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        if i == j {
+            return Ok(matrices[i].clone());
+        }
+        let k = split[i][j];
+        // single operands are borrowed rather than copied
+        let left = (i < k)
+            .then(|| self.multiply_range(matrices, split, i, k))
+            .transpose()?;
+        let right = (k + 1 < j)
+            .then(|| self.multiply_range(matrices, split, k + 1, j))
+            .transpose()?;
+        self.multiply(
+            left.as_ref().unwrap_or(matrices[i]),
+            right.as_ref().unwrap_or(matrices[j]),
+        )
+    }
+}
+
+/// The classic matrix-chain DP over `dims`, where matrix `i` is
+/// `dims[i] x dims[i + 1]`. Returns the minimal cost and, for every range
+/// `i..=j`, the index `k` after which it is split.
+fn chain_order(dims: &[usize]) -> (usize, Vec<Vec<usize>>) {
+    let n = dims.len() - 1;
+    let mut cost = vec![vec![0usize; n]; n];
+    let mut split = vec![vec![0; n]; n];
+    for len in 2..=n {
+        for i in 0..=n - len {
+            let j = i + len - 1;
+            cost[i][j] = usize::MAX;
+            for k in i..j {
+                let c = cost[i][k]
+                    .saturating_add(cost[k + 1][j])
+                    .saturating_add(dims[i] * dims[k + 1] * dims[j + 1]);
+                if c < cost[i][j] {
+                    cost[i][j] = c;
+                    split[i][j] = k;
+                }
+            }
+        }
+    }
+    (cost[0][n - 1], split)
+}
+
+impl<T> Matrix<T>
+where
+    T: Semiring,
+{
+    pub fn pow(&self, n: u32) -> Result<Matrix<T>, MatrixError> {
+        MatrixExecutor::global().pow(self, n)
+    }
+}
+
+#[test]
+fn test_pow_by_squaring() -> anyhow::Result<()> {
+    let fib = Matrix::from_rows([[1u64, 1], [1, 0]])?;
+    assert_eq!(fib.pow(10)?, Matrix::from_rows([[89, 55], [55, 34]])?);
+    assert_eq!(fib.pow(1)?, fib);
+    assert_eq!(fib.pow(0)?, Matrix::identity(2));
+    assert!(matches!(
+        Matrix::<u64>::zeros(2, 3).pow(2),
+        Err(MatrixError::NotSquare { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_multiply_chain_picks_cheapest_order() -> anyhow::Result<()> {
+    let (cost, split) = chain_order(&[30, 35, 15, 5, 10, 20, 25]);
+    assert_eq!(cost, 15125);
+    assert_eq!((split[0][5], split[0][2], split[3][5]), (2, 0, 4));
+
+    let a = Matrix::from_fn(3, 1, |i, _| i as i64 + 1);
+    let b = Matrix::from_fn(1, 4, |_, j| j as i64 - 2);
+    let c = Matrix::from_fn(4, 2, |i, j| (i * 2 + j) as i64);
+    let expected = super::multiply(&super::multiply(&a, &b)?, &c)?;
+    assert_eq!(multiply_chain(&[&a, &b, &c])?, expected);
+    assert_eq!(multiply_chain(&[&a])?, a);
+    assert!(matches!(
+        multiply_chain::<i64>(&[]),
+        Err(MatrixError::EmptyChain)
+    ));
+    assert!(multiply_chain(&[&a, &c]).is_err());
+    Ok(())
+}
@@ -0,0 +1,267 @@
+use std::{
+    fmt,
+    io::{Read, Write},
+    str::FromStr,
+};
+
+use super::{Layout, Matrix};
+use crate::error::MatrixError;
+
+/// A CSV column, by header name or by 0-based position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvColumn {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for CsvColumn {
+    fn from(idx: usize) -> Self {
+        CsvColumn::Index(idx)
+    }
+}
+
+impl From<&str> for CsvColumn {
+    fn from(name: &str) -> Self {
+        CsvColumn::Name(name.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    delimiter: u8,
+    has_headers: bool,
+    columns: Option<Vec<CsvColumn>>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: true,
+            columns: None,
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Whether the first record is a header row; defaults to `true`.
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Reads only these columns, in this order, instead of every column.
+    /// When writing, there must be one per matrix column; named columns
+    /// become the header row.
+    pub fn columns<C: Into<CsvColumn>>(mut self, columns: impl IntoIterator<Item = C>) -> Self {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    /// Reads one matrix row per CSV record. Records are parsed as they are
+    /// read, so only the matrix itself is kept in memory. Parse errors give
+    /// the record's line and the 1-based field number as the column.
+    pub fn from_csv_reader(reader: impl Read, options: &CsvOptions) -> Result<Self, MatrixError> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .has_headers(options.has_headers)
+            .from_reader(reader);
+        let selected = match &options.columns {
+            Some(columns) => Some(resolve_columns(&mut reader, columns, options.has_headers)?),
+            None => None,
+        };
+
+        let mut data = Vec::new();
+        let mut row = 0;
+        let mut col = match &selected {
+            Some(fields) => fields.len(),
+            None if options.has_headers => reader.headers()?.len(),
+            None => 0,
+        };
+        let mut record = ::csv::StringRecord::new();
+        while reader.read_record(&mut record)? {
+            let line = record.position().map_or(0, |p| p.line() as usize);
+            let parse = |field: usize| {
+                let value = record.get(field).ok_or_else(|| MatrixError::Parse {
+                    line,
+                    column: field + 1,
+                    message: format!("missing field {}", field + 1),
+                })?;
+                value.trim().parse::<T>().map_err(|e| MatrixError::Parse {
+                    line,
+                    column: field + 1,
+                    message: format!("invalid value `{}`: {}", value, e),
+                })
+            };
+            match &selected {
+                Some(fields) => {
+                    for &field in fields {
+                        data.push(parse(field)?);
+                    }
+                }
+                None => {
+                    if row == 0 && !options.has_headers {
+                        col = record.len();
+                    }
+                    for field in 0..col {
+                        data.push(parse(field)?);
+                    }
+                }
+            }
+            row += 1;
+        }
+        Ok(Matrix {
+            data,
+            row,
+            col,
+            layout: Layout::RowMajor,
+        })
+    }
+}
+
+impl<T: fmt::Display> Matrix<T> {
+    /// Writes one CSV record per row. With `has_headers`, which is the
+    /// default, a header row of the selected column names is written first;
+    /// columns without a name are headed by their position, so
+    /// `CsvOptions::new()` writes `0,1,...` and reads it back unchanged.
+    /// Every column is written; selected columns must match them one to one.
+    pub fn to_csv_writer(
+        &self,
+        writer: impl Write,
+        options: &CsvOptions,
+    ) -> Result<(), MatrixError> {
+        if let Some(columns) = &options.columns {
+            if columns.len() != self.col {
+                return Err(MatrixError::CsvColumns {
+                    expected: self.col,
+                    found: columns.len(),
+                });
+            }
+        }
+        let mut writer = ::csv::WriterBuilder::new()
+            .delimiter(options.delimiter)
+            .from_writer(writer);
+        if options.has_headers {
+            let header =
+                (0..self.col).map(|j| match options.columns.as_ref().and_then(|c| c.get(j)) {
+                    Some(CsvColumn::Name(name)) => name.clone(),
+                    _ => j.to_string(),
+                });
+            writer.write_record(header)?;
+        }
+        let mut record = Vec::with_capacity(self.col);
+        for row in self.iter_rows() {
+            record.clear();
+            record.extend(row.map(|value| value.to_string()));
+            writer.write_record(&record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Maps the requested columns to field positions, looking names up in the
+/// header row.
+fn resolve_columns(
+    reader: &mut ::csv::Reader<impl Read>,
+    columns: &[CsvColumn],
+    has_headers: bool,
+) -> Result<Vec<usize>, MatrixError> {
+    let headers = if has_headers {
+        Some(reader.headers()?.clone())
+    } else {
+        None
+    };
+    columns
+        .iter()
+        .map(|column| match column {
+            CsvColumn::Index(idx) => Ok(*idx),
+            CsvColumn::Name(name) => headers
+                .as_ref()
+                .and_then(|h| h.iter().position(|field| field.trim() == name))
+                .ok_or_else(|| MatrixError::Parse {
+                    line: 1,
+                    column: 1,
+                    message: format!("no column named `{}`", name),
+                }),
+        })
+        .collect()
+}
+
+#[test]
+fn test_csv_selected_columns() -> anyhow::Result<()> {
+    let input = "Name,Position,Kit Number,Age\nSzczesny,Goalkeeper,1,29\nPerin,Goalkeeper,37,26\n";
+    let options = CsvOptions::new().columns(["Age", "Kit Number"]);
+    let a = Matrix::<u32>::from_csv_reader(input.as_bytes(), &options)?;
+    assert_eq!(a, Matrix::from_rows([[29, 1], [26, 37]])?);
+
+    let mut out = Vec::new();
+    a.to_csv_writer(&mut out, &options)?;
+    assert_eq!(String::from_utf8(out)?, "Age,Kit Number\n29,1\n26,37\n");
+
+    let err = a.to_csv_writer(Vec::new(), &CsvOptions::new().columns(["Age"]));
+    assert!(matches!(
+        err,
+        Err(MatrixError::CsvColumns {
+            expected: 2,
+            found: 1
+        })
+    ));
+
+    let err = Matrix::<u32>::from_csv_reader(input.as_bytes(), &CsvOptions::new().columns([0]));
+    assert!(matches!(
+        err,
+        Err(MatrixError::Parse {
+            line: 2,
+            column: 1,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_csv_round_trip_without_headers() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1.5, -2.0], [0.25, 4.0]])?;
+    let options = CsvOptions::new().has_headers(false).delimiter(b';');
+    let mut out = Vec::new();
+    a.to_csv_writer(&mut out, &options)?;
+    assert_eq!(String::from_utf8(out.clone())?, "1.5;-2\n0.25;4\n");
+    assert_eq!(Matrix::<f64>::from_csv_reader(&out[..], &options)?, a);
+
+    // the default options head unnamed columns with their positions
+    let mut out = Vec::new();
+    a.to_csv_writer(&mut out, &CsvOptions::new())?;
+    assert_eq!(String::from_utf8(out.clone())?, "0,1\n1.5,-2\n0.25,4\n");
+    assert_eq!(
+        Matrix::<f64>::from_csv_reader(&out[..], &CsvOptions::new())?,
+        a
+    );
+    Ok(())
+}
+
+#[test]
+fn test_csv_header_without_rows() -> anyhow::Result<()> {
+    let a = Matrix::<i32>::zeros(0, 3);
+    let mut out = Vec::new();
+    a.to_csv_writer(&mut out, &CsvOptions::new())?;
+    assert_eq!(String::from_utf8(out.clone())?, "0,1,2\n");
+    let b = Matrix::<i32>::from_csv_reader(&out[..], &CsvOptions::new())?;
+    assert_eq!(b.shape(), (0, 3));
+    Ok(())
+}
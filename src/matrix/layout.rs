@@ -0,0 +1,144 @@
+use super::{Matrix, MatrixView};
+use crate::error::MatrixError;
+
+/// How a matrix orders its elements in memory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Layout {
+    #[default]
+    RowMajor,
+    ColMajor,
+}
+
+impl Layout {
+    pub fn is_row_major(&self) -> bool {
+        *self == Layout::RowMajor
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Builds a `row x col` matrix from column-major `data`.
+    pub fn from_col_major(
+        data: impl Into<Vec<T>>,
+        row: usize,
+        col: usize,
+    ) -> Result<Self, MatrixError> {
+        let mut m = Matrix::new(data, col, row)?;
+        (m.row, m.col, m.layout) = (row, col, Layout::ColMajor);
+        Ok(m)
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// The same matrix stored in `layout`.
+    pub fn to_layout(&self, layout: Layout) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        if layout == self.layout {
+            return self.clone();
+        }
+        self.view().to_layout(layout)
+    }
+
+    /// Like `to_layout`, but reuses the buffer when it already has `layout`.
+    pub fn into_layout(self, layout: Layout) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        if layout == self.layout {
+            return self;
+        }
+        self.view().to_layout(layout)
+    }
+
+    /// Position of `(i, j)` in `data`.
+    pub(super) fn offset(&self, i: usize, j: usize) -> usize {
+        match self.layout {
+            Layout::RowMajor => i * self.col + j,
+            Layout::ColMajor => j * self.row + i,
+        }
+    }
+
+    /// The `k`-th contiguous run of `data`: a row of a row-major matrix or a
+    /// column of a column-major one.
+    pub(super) fn lane(&self, k: usize) -> &[T] {
+        let len = match self.layout {
+            Layout::RowMajor => self.col,
+            Layout::ColMajor => self.row,
+        };
+        &self.data[k * len..(k + 1) * len]
+    }
+}
+
+impl<T> MatrixView<'_, T> {
+    /// Copies the view into a matrix stored in `layout`.
+    pub fn to_layout(&self, layout: Layout) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        let data = match layout {
+            Layout::RowMajor => self.iter().cloned().collect(),
+            // column-major order is the row-major order of the transpose
+            Layout::ColMajor => self.transpose().iter().cloned().collect(),
+        };
+        Matrix {
+            data,
+            row: self.rows(),
+            col: self.cols(),
+            layout,
+        }
+    }
+}
+
+/// Matrices are equal when their shapes and elements are, whatever their
+/// layouts.
+impl<T: PartialEq> PartialEq for Matrix<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.shape() != other.shape() {
+            return false;
+        }
+        if self.layout == other.layout {
+            return self.data == other.data;
+        }
+        self.view().iter().eq(other.view().iter())
+    }
+}
+
+#[test]
+fn test_layout_conversions() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1, 2, 3], [4, 5, 6]])?;
+    let c = a.to_layout(Layout::ColMajor);
+    assert_eq!(c.layout(), Layout::ColMajor);
+    assert_eq!(c.as_slice(), &[1, 4, 2, 5, 3, 6]);
+    assert_eq!(c, a);
+    assert_eq!(c[(1, 2)], 6);
+    assert_eq!(c.row(1).iter().copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+    assert_eq!(Matrix::from_col_major([1, 4, 2, 5, 3, 6], 2, 3)?, a);
+    assert_eq!(
+        c.clone().into_layout(Layout::RowMajor).as_slice(),
+        a.as_slice()
+    );
+    assert_eq!(c.to_string(), a.to_string());
+    Ok(())
+}
+
+#[test]
+fn test_multiply_mixed_layouts() -> anyhow::Result<()> {
+    let a = Matrix::from_fn(5, 4, |i, j| (i * 4 + j) as i64);
+    let b = Matrix::from_fn(4, 3, |i, j| (i + 2 * j) as i64 - 3);
+    let expected = super::multiply(&a, &b)?;
+    let executor = crate::MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(crate::Granularity::Tile(2, 2))
+        .build()?;
+    let (ac, bc) = (a.to_layout(Layout::ColMajor), b.to_layout(Layout::ColMajor));
+    assert_eq!(executor.multiply(&ac, &b)?, expected);
+    assert_eq!(executor.multiply(&a, &bc)?, expected);
+    assert_eq!(ac.clone() * bc.clone(), expected);
+    assert_eq!(&ac + &a, &a * 2);
+    assert_eq!(ac.transpose(), a.transpose());
+    Ok(())
+}
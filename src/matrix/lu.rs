@@ -0,0 +1,304 @@
+use std::{
+    ops::{Add, Div, Mul, Neg, Sub},
+    sync::Arc,
+};
+
+use super::{Layout, Matrix, MatrixExecutor};
+use crate::{error::MatrixError, semiring::Semiring, vector::Vector};
+
+/// Floating point element types supported by the decompositions.
+pub trait Real:
+    Semiring
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    /// The machine epsilon.
+    fn epsilon() -> Self;
+    fn from_usize(n: usize) -> Self;
+}
+
+impl Real for f32 {
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn epsilon() -> Self {
+        f32::EPSILON
+    }
+
+    fn from_usize(n: usize) -> Self {
+        n as f32
+    }
+}
+
+impl Real for f64 {
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn epsilon() -> Self {
+        f64::EPSILON
+    }
+
+    fn from_usize(n: usize) -> Self {
+        n as f64
+    }
+}
+
+/// `P * A = L * U` with partial pivoting. `L` (unit diagonal, not stored)
+/// and `U` share one matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LuDecomposition<T> {
+    lu: Matrix<T>,
+    perm: Vec<usize>,
+    swaps: usize,
+}
+
+impl MatrixExecutor {
+    /// LU decomposition; the rows below each pivot are eliminated on the
+    /// workers.
+    pub fn lu<T: Real>(&self, a: &Matrix<T>) -> Result<LuDecomposition<T>, MatrixError> {
+        let n = a.row;
+        if a.col != n {
+            return Err(MatrixError::NotSquare {
+                rows: a.row,
+                cols: a.col,
+            });
+        }
+        let mut rows = a
+            .iter_rows()
+            .map(|row| row.copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        // a pivot this small relative to its column is rounding noise of an
+        // exact zero; scaling per column keeps badly scaled matrices
+        // invertible
+        let tolerance = (0..n)
+            .map(|k| {
+                let scale =
+                    a.col(k).iter().fold(
+                        T::zero(),
+                        |max, &x| if x.abs() > max { x.abs() } else { max },
+                    );
+                T::from_usize(n) * T::epsilon() * scale
+            })
+            .collect::<Vec<_>>();
+        let mut perm = (0..n).collect::<Vec<_>>();
+        let mut swaps = 0;
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&x, &y| {
+                    rows[x][k]
+                        .abs()
+                        .partial_cmp(&rows[y][k].abs())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap_or(k);
+            if rows[p][k].abs() <= tolerance[k] {
+                return Err(MatrixError::Singular { col: k });
+            }
+            if p != k {
+                rows.swap(p, k);
+                perm.swap(p, k);
+                swaps += 1;
+            }
+            let pivot = Arc::new(rows[k].clone());
+            let trailing = rows.split_off(k + 1);
+            let work = trailing.len() * (n - k);
+            let updated = self.map_owned(trailing, work, move |mut row: Vec<T>| {
+                let factor = row[k] / pivot[k];
+                row[k] = factor;
+                for j in k + 1..n {
+                    row[j] = row[j] - factor * pivot[j];
+                }
+                Ok(row)
+            })?;
+            rows.extend(updated);
+        }
+        Ok(LuDecomposition {
+            lu: Matrix {
+                data: rows.concat(),
+                row: n,
+                col: n,
+                layout: Layout::RowMajor,
+            },
+            perm,
+            swaps,
+        })
+    }
+
+    pub fn inverse<T: Real>(&self, a: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let lu = Arc::new(self.lu(a)?);
+        let n = a.row;
+        // one column of the inverse per unit vector, solved on the workers
+        let cols = self.map_indexed(n, 1, move |j| {
+            let e = (0..n)
+                .map(|i| if i == j { T::one() } else { T::zero() })
+                .collect::<Vec<_>>();
+            Ok(lu.substitute(e))
+        })?;
+        Ok(Matrix::from_fn(n, n, |i, j| cols[j][i]))
+    }
+}
+
+impl<T: Real> LuDecomposition<T> {
+    /// The unit lower triangular factor.
+    pub fn l(&self) -> Matrix<T> {
+        Matrix::from_fn(self.lu.row, self.lu.col, |i, j| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => self.lu[(i, j)],
+            std::cmp::Ordering::Equal => T::one(),
+            std::cmp::Ordering::Less => T::zero(),
+        })
+    }
+
+    /// The upper triangular factor.
+    pub fn u(&self) -> Matrix<T> {
+        Matrix::from_fn(self.lu.row, self.lu.col, |i, j| {
+            if i <= j {
+                self.lu[(i, j)]
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    /// Row `i` of `P * A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    pub fn determinant(&self) -> T {
+        let det = (0..self.lu.row).fold(T::one(), |det, i| det * self.lu[(i, i)]);
+        if self.swaps % 2 == 1 {
+            -det
+        } else {
+            det
+        }
+    }
+
+    pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, MatrixError> {
+        if b.len() != self.lu.row {
+            return Err(MatrixError::LengthMismatch {
+                left: self.lu.row,
+                right: b.len(),
+            });
+        }
+        Ok(Vector::new(self.substitute(b.to_vec())))
+    }
+
+    /// Forward and back substitution of `b` through `L` and `U`.
+    fn substitute(&self, b: Vec<T>) -> Vec<T> {
+        let n = self.lu.row;
+        let mut x = self.perm.iter().map(|&p| b[p]).collect::<Vec<_>>();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] = x[i] - self.lu[(i, j)] * x[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] = x[i] - self.lu[(i, j)] * x[j];
+            }
+            x[i] = x[i] / self.lu[(i, i)];
+        }
+        x
+    }
+}
+
+impl<T: Real> Matrix<T> {
+    pub fn lu(&self) -> Result<LuDecomposition<T>, MatrixError> {
+        MatrixExecutor::global().lu(self)
+    }
+
+    /// The determinant; zero for singular matrices.
+    pub fn determinant(&self) -> Result<T, MatrixError> {
+        match self.lu() {
+            Ok(lu) => Ok(lu.determinant()),
+            Err(MatrixError::Singular { .. }) => Ok(T::zero()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        MatrixExecutor::global().inverse(self)
+    }
+
+    /// Solves `self * x = b`.
+    pub fn solve(&self, b: &Vector<T>) -> Result<Vector<T>, MatrixError> {
+        self.lu()?.solve(b)
+    }
+}
+
+#[cfg(test)]
+fn assert_close(a: &Matrix<f64>, b: &Matrix<f64>) {
+    assert_eq!(a.shape(), b.shape());
+    for (x, y) in a.data.iter().zip(&b.data) {
+        assert!((x - y).abs() < 1e-9, "{} != {}", a, b);
+    }
+}
+
+#[test]
+fn test_lu_reconstructs_matrix() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[2.0, 1.0, 1.0], [4.0, -6.0, 0.0], [-2.0, 7.0, 2.0]])?;
+    let executor = MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(crate::Granularity::Rows(1))
+        .build()?;
+    let lu = executor.lu(&a)?;
+    let pa = Matrix::from_fn(3, 3, |i, j| a[(lu.permutation()[i], j)]);
+    assert_close(&super::multiply(&lu.l(), &lu.u())?, &pa);
+    assert!((lu.determinant() - -16.0).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_inverse_and_solve() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[4.0, 7.0], [2.0, 6.0]])?;
+    let inv = a.inverse()?;
+    assert_close(&super::multiply(&a, &inv)?, &Matrix::identity(2));
+    let x = a.solve(&Vector::new([1.0, 2.0]))?;
+    assert!((x[0] - -0.8).abs() < 1e-9 && (x[1] - 0.6).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_singular_matrix() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1.0f32, 2.0], [2.0, 4.0]])?;
+    assert!(matches!(a.inverse(), Err(MatrixError::Singular { col: 1 })));
+    assert_eq!(a.determinant()?, 0.0);
+    // elimination leaves a pivot of about 1e-15 rather than exactly zero
+    let b = Matrix::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]])?;
+    assert!(matches!(b.inverse(), Err(MatrixError::Singular { col: 2 })));
+    assert_eq!(b.determinant()?, 0.0);
+    assert!(matches!(
+        Matrix::<f64>::zeros(2, 2).lu(),
+        Err(MatrixError::Singular { col: 0 })
+    ));
+    // badly scaled but invertible
+    let c = Matrix::from_rows([[1.0, 0.0], [0.0, 1e17]])?;
+    assert_close(
+        &c.inverse()?,
+        &Matrix::from_rows([[1.0, 0.0], [0.0, 1e-17]])?,
+    );
+    assert_eq!(c.determinant()?, 1e17);
+    let d = Matrix::from_rows([[1e-20, 0.0], [0.0, 1.0]])?;
+    assert_eq!(d.determinant()?, 1e-20);
+    assert!(matches!(
+        Matrix::<f64>::zeros(2, 3).lu(),
+        Err(MatrixError::NotSquare { rows: 2, cols: 3 })
+    ));
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::{ops::Mul, sync::Arc};
+
+use super::{packed, Layout, Matrix, MatrixExecutor};
+use crate::{
+    error::MatrixError,
+    semiring::Semiring,
+    vector::{dot, Vector},
+};
+
+/// Computes `a * v`, treating `v` as a column vector.
+pub fn multiply_vector<T>(a: &Matrix<T>, v: &Vector<T>) -> Result<Vector<T>, MatrixError>
+where
+    T: Semiring,
+{
+    MatrixExecutor::global().multiply_vector(a, v)
+}
+
+/// Computes `v * a`, treating `v` as a row vector.
+pub fn vector_multiply<T>(v: &Vector<T>, a: &Matrix<T>) -> Result<Vector<T>, MatrixError>
+where
+    T: Semiring,
+{
+    MatrixExecutor::global().vector_multiply(v, a)
+}
+
+impl MatrixExecutor {
+    pub fn multiply_vector<T>(&self, a: &Matrix<T>, v: &Vector<T>) -> Result<Vector<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        self.multiply_vector_shared(
+            &Arc::new(a.to_layout(Layout::RowMajor)),
+            &Arc::new(v.clone()),
+        )
+    }
+
+    /// Like `multiply_vector`, but hands the workers the caller's operands;
+    /// `a` is only copied if it is not row-major.
+    pub fn multiply_vector_shared<T>(
+        &self,
+        a: &Arc<Matrix<T>>,
+        v: &Arc<Vector<T>>,
+    ) -> Result<Vector<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        if a.col != v.len() {
+            return Err(MatrixError::DimensionMismatch {
+                left: a.shape(),
+                right: (v.len(), 1),
+            });
+        }
+        let (a, v) = (packed(a.clone(), Layout::RowMajor), v.clone());
+        let data = self.map_indexed(a.row, 1, move |i| dot(a.lane(i).iter(), v.iter()))?;
+        Ok(Vector::new(data))
+    }
+
+    pub fn vector_multiply<T>(&self, v: &Vector<T>, a: &Matrix<T>) -> Result<Vector<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        self.vector_multiply_shared(
+            &Arc::new(v.clone()),
+            &Arc::new(a.to_layout(Layout::ColMajor)),
+        )
+    }
+
+    /// Like `vector_multiply`, but hands the workers the caller's operands;
+    /// `a` is only copied if it is not column-major.
+    pub fn vector_multiply_shared<T>(
+        &self,
+        v: &Arc<Vector<T>>,
+        a: &Arc<Matrix<T>>,
+    ) -> Result<Vector<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        if v.len() != a.row {
+            return Err(MatrixError::DimensionMismatch {
+                left: (1, v.len()),
+                right: a.shape(),
+            });
+        }
+        let (a, v) = (packed(a.clone(), Layout::ColMajor), v.clone());
+        let data = self.map_indexed(a.col, a.col, move |j| dot(v.iter(), a.lane(j).iter()))?;
+        Ok(Vector::new(data))
+    }
+}
+
+impl<T> Mul<&Vector<T>> for &Matrix<T>
+where
+    T: Semiring,
+{
+    type Output = Vector<T>;
+    fn mul(self, rhs: &Vector<T>) -> Self::Output {
+        multiply_vector(self, rhs).expect("Matrix vector multiply error")
+    }
+}
+
+impl<T> Mul<Vector<T>> for Matrix<T>
+where
+    T: Semiring,
+{
+    type Output = Vector<T>;
+    fn mul(self, rhs: Vector<T>) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<T> Mul<&Matrix<T>> for &Vector<T>
+where
+    T: Semiring,
+{
+    type Output = Vector<T>;
+    fn mul(self, rhs: &Matrix<T>) -> Self::Output {
+        vector_multiply(self, rhs).expect("Vector matrix multiply error")
+    }
+}
+
+impl<T> Mul<Matrix<T>> for Vector<T>
+where
+    T: Semiring,
+{
+    type Output = Vector<T>;
+    fn mul(self, rhs: Matrix<T>) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+#[test]
+fn test_matrix_vector_multiply() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1, 2, 3], [4, 5, 6]])?;
+    assert_eq!(*(&a * &Vector::new([1, 0, 2])), vec![7, 16]);
+    assert_eq!(*(&Vector::new([1, 1]) * &a), vec![5, 7, 9]);
+
+    let executor = MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(crate::Granularity::Rows(1))
+        .build()?;
+    let v = executor.multiply_vector(&a, &Vector::new([1, 1, 1]))?;
+    assert_eq!(*v, vec![6, 15]);
+    assert!(matches!(
+        executor.multiply_vector(&a, &Vector::new([1, 1])),
+        Err(MatrixError::DimensionMismatch {
+            left: (2, 3),
+            right: (2, 1)
+        })
+    ));
+
+    let shared = Arc::new(a.clone());
+    let v = Arc::new(Vector::new([1, 1, 1]));
+    assert_eq!(*executor.multiply_vector_shared(&shared, &v)?, vec![6, 15]);
+    let c = Arc::new(a.to_layout(Layout::ColMajor));
+    assert_eq!(*executor.multiply_vector_shared(&c, &v)?, vec![6, 15]);
+    let u = Arc::new(Vector::new([1, 1]));
+    assert_eq!(*executor.vector_multiply_shared(&u, &c)?, vec![5, 7, 9]);
+    assert_eq!(
+        *executor.vector_multiply_shared(&u, &shared)?,
+        vec![5, 7, 9]
+    );
+    Ok(())
+}
@@ -0,0 +1,1083 @@
+use core::fmt;
+use std::{
+    ops::{Index, IndexMut, Mul, Range},
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use tokio::sync::oneshot;
+
+mod arithmetic;
+mod batch;
+mod binary;
+mod chain;
+mod csv;
+mod layout;
+mod lu;
+mod matvec;
+mod overflow;
+mod parse;
+mod reduce;
+mod smatrix;
+mod sparse;
+mod strassen;
+mod view;
+
+pub use arithmetic::*;
+pub use batch::*;
+pub use binary::*;
+pub use chain::*;
+pub use csv::*;
+pub use layout::*;
+pub use lu::*;
+pub use matvec::*;
+pub use overflow::*;
+pub use smatrix::*;
+pub use sparse::*;
+pub use strassen::*;
+pub use view::*;
+
+use crate::{
+    error::{MatrixError, WorkerFailure},
+    executor::{Granularity, MatrixExecutor},
+    semiring::Semiring,
+    vector::dot,
+};
+
+/// Elementwise operations on at most this many elements run on the calling
+/// thread, and no worker message covers fewer.
+const ELEMENTWISE_CHUNK: usize = 16 * 1024;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "MatrixData<T>"))]
+pub struct Matrix<T> {
+    data: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(rename = "rows"))]
+    row: usize,
+    #[cfg_attr(feature = "serde", serde(rename = "cols"))]
+    col: usize,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Layout::is_row_major"))]
+    layout: Layout,
+}
+
+/// The serialized form of a `Matrix`, checked by `Matrix::new` before it is
+/// accepted.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct MatrixData<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+    #[serde(default)]
+    layout: Layout,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<MatrixData<T>> for Matrix<T> {
+    type Error = MatrixError;
+
+    fn try_from(m: MatrixData<T>) -> Result<Self, Self::Error> {
+        match m.layout {
+            Layout::RowMajor => Matrix::new(m.data, m.rows, m.cols),
+            Layout::ColMajor => Matrix::from_col_major(m.data, m.rows, m.cols),
+        }
+    }
+}
+
+/// Asks a worker for the block `rows x cols` of `a * b`. The operands are
+/// shared, so a message only carries indices.
+pub struct MsgInput<T> {
+    idx: usize,
+    a: Arc<Matrix<T>>,
+    b: Arc<Matrix<T>>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+/// The requested block of the product, row-major.
+#[derive(Debug)]
+pub struct MsgOutput<T> {
+    idx: usize,
+    values: Vec<T>,
+}
+
+pub struct Msg<T> {
+    input: MsgInput<T>,
+    sender: oneshot::Sender<MsgResult<T>>,
+}
+
+pub type MsgResult<T> = Result<MsgOutput<T>, MatrixError>;
+
+/// `a * b`; either operand may be a `Matrix` or a borrowed view of one.
+pub fn multiply<T, A, B>(a: &A, b: &B) -> Result<Matrix<T>, MatrixError>
+where
+    T: Semiring,
+    A: AsMatrixView<T>,
+    B: AsMatrixView<T>,
+{
+    MatrixExecutor::global().multiply(a, b)
+}
+
+pub async fn multiply_async<T, A, B>(a: &A, b: &B) -> Result<Matrix<T>, MatrixError>
+where
+    T: Semiring,
+    A: AsMatrixView<T>,
+    B: AsMatrixView<T>,
+{
+    MatrixExecutor::global().multiply_async(a, b).await
+}
+
+impl MatrixExecutor {
+    /// Views are computed on in place below the sequential threshold, and
+    /// copied once for the workers above it.
+    pub fn multiply<T, A, B>(&self, a: &A, b: &B) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+        A: AsMatrixView<T>,
+        B: AsMatrixView<T>,
+    {
+        let (a, b) = (a.as_view(), b.as_view());
+        check_multiply(a.shape(), b.shape())?;
+        if a.rows() * a.cols() * b.cols() <= self.sequential_threshold() {
+            return multiply_sequential(a, b);
+        }
+        self.multiply_tiles(
+            Arc::new(a.to_layout(Layout::RowMajor)),
+            Arc::new(b.to_layout(Layout::ColMajor)),
+        )
+    }
+
+    /// Like `multiply`, but hands the workers the caller's operands instead
+    /// of copying them once per call.
+    pub fn multiply_shared<T>(
+        &self,
+        a: &Arc<Matrix<T>>,
+        b: &Arc<Matrix<T>>,
+    ) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        check_multiply(a.shape(), b.shape())?;
+        if a.row * a.col * b.col <= self.sequential_threshold() {
+            return multiply_sequential(a.view(), b.view());
+        }
+        self.multiply_tiles(a.clone(), b.clone())
+    }
+
+    /// Async version of `multiply`: the work still runs on the executor's
+    /// threads, and the caller awaits the results instead of blocking.
+    pub async fn multiply_async<T, A, B>(&self, a: &A, b: &B) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+        A: AsMatrixView<T>,
+        B: AsMatrixView<T>,
+    {
+        let (a, b) = (a.as_view(), b.as_view());
+        check_multiply(a.shape(), b.shape())?;
+        if a.rows() * a.cols() * b.cols() <= self.sequential_threshold() {
+            return multiply_sequential(a, b);
+        }
+        self.dispatch_tiles(
+            Arc::new(a.to_layout(Layout::RowMajor)),
+            Arc::new(b.to_layout(Layout::ColMajor)),
+        )?
+        .wait_async()
+        .await
+    }
+
+    /// Starts `a * b` without waiting for the workers; products under the
+    /// sequential threshold are computed right away.
+    fn start_multiply<T>(
+        &self,
+        a: Arc<Matrix<T>>,
+        b: Arc<Matrix<T>>,
+    ) -> Result<PendingProduct<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        if a.row * a.col * b.col <= self.sequential_threshold() {
+            return Ok(PendingProduct::ready(multiply_sequential(
+                a.view(),
+                b.view(),
+            )?));
+        }
+        self.dispatch_tiles(a, b)
+    }
+
+    fn multiply_tiles<T>(
+        &self,
+        a: Arc<Matrix<T>>,
+        b: Arc<Matrix<T>>,
+    ) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        self.dispatch_tiles(a, b)?.wait()
+    }
+
+    fn dispatch_tiles<T>(
+        &self,
+        a: Arc<Matrix<T>>,
+        b: Arc<Matrix<T>>,
+    ) -> Result<PendingProduct<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        let (tile_rows, tile_cols) = match self.granularity() {
+            Granularity::Auto => (self.auto_chunk(a.row), b.col),
+            Granularity::Cell => (1, 1),
+            Granularity::Rows(n) => (n, b.col),
+            Granularity::Tile(rows, cols) => (rows, cols),
+        };
+        let (tile_rows, tile_cols) = (tile_rows.max(1), tile_cols.max(1));
+        // packed once, so workers read rows of `a` and columns of `b` from
+        // contiguous memory
+        let a = packed(a, Layout::RowMajor);
+        let b = packed(b, Layout::ColMajor);
+        let mut pending = PendingProduct {
+            data: vec![T::zero(); a.row * b.col],
+            row: a.row,
+            col: b.col,
+            tiles: Vec::new(),
+            receivers: Vec::new(),
+        };
+
+        for r0 in (0..a.row).step_by(tile_rows) {
+            for c0 in (0..b.col).step_by(tile_cols) {
+                let rows = r0..(r0 + tile_rows).min(a.row);
+                let cols = c0..(c0 + tile_cols).min(b.col);
+                let input = MsgInput::new(
+                    pending.tiles.len(),
+                    a.clone(),
+                    b.clone(),
+                    rows.clone(),
+                    cols.clone(),
+                );
+                let (tx, rx) = oneshot::channel();
+                let msg = Msg { input, sender: tx };
+                self.execute(move || msg.process())?;
+                pending.tiles.push((rows, cols));
+                pending.receivers.push(rx);
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Computes `f(idx)` for every `idx < len` on the workers, in chunks sized
+    /// by the executor's granularity. `cols` maps a flat index back to its
+    /// cell when reporting errors.
+    pub(crate) fn map_indexed<U, F>(
+        &self,
+        len: usize,
+        cols: usize,
+        f: F,
+    ) -> Result<Vec<U>, MatrixError>
+    where
+        U: Send + 'static,
+        F: Fn(usize) -> Result<U, MatrixError> + Send + Sync + 'static,
+    {
+        self.map_chunked(len, cols, 1, f)
+    }
+
+    /// Like `map_indexed`, but no message covers fewer than `min_chunk`
+    /// indices.
+    pub(crate) fn map_chunked<U, F>(
+        &self,
+        len: usize,
+        cols: usize,
+        min_chunk: usize,
+        f: F,
+    ) -> Result<Vec<U>, MatrixError>
+    where
+        U: Send + 'static,
+        F: Fn(usize) -> Result<U, MatrixError> + Send + Sync + 'static,
+    {
+        if len <= self.sequential_threshold() {
+            return (0..len).map(f).collect();
+        }
+        let chunk = match self.granularity() {
+            Granularity::Auto => self.auto_chunk(len),
+            Granularity::Cell => 1,
+            Granularity::Rows(n) => n.saturating_mul(cols),
+            Granularity::Tile(rows, cols) => rows.saturating_mul(cols),
+        }
+        .max(min_chunk)
+        .max(1);
+        let f = Arc::new(f);
+        let mut receivers = Vec::new();
+        for start in (0..len).step_by(chunk) {
+            let range = start..(start + chunk).min(len);
+            let (tx, rx) = oneshot::channel::<MsgResult<U>>();
+            let f = f.clone();
+            let idx = receivers.len();
+            self.execute(move || {
+                let result =
+                    compute_chunk(range, cols, &*f).map(|values| MsgOutput { idx, values });
+                let _ = tx.send(result);
+            })?;
+            receivers.push((start, rx));
+        }
+        let mut data = Vec::with_capacity(len);
+        for (start, rx) in receivers {
+            let (i, j) = cell(start, cols);
+            let output = rx
+                .blocking_recv()
+                .map_err(|_| MatrixError::worker(i, j, WorkerFailure::Disconnected))??;
+            data.extend(output.values);
+        }
+        Ok(data)
+    }
+
+    /// Moves `items` to the workers in chunks and returns `f(item)` for each,
+    /// in order. `work` is the total number of element updates, compared
+    /// against the sequential threshold. Errors report the item index as the
+    /// row of the failing cell.
+    pub(crate) fn map_owned<I, U, F>(
+        &self,
+        items: Vec<I>,
+        work: usize,
+        f: F,
+    ) -> Result<Vec<U>, MatrixError>
+    where
+        I: Send + 'static,
+        U: Send + 'static,
+        F: Fn(I) -> Result<U, MatrixError> + Send + Sync + 'static,
+    {
+        if work <= self.sequential_threshold() {
+            return items.into_iter().map(f).collect();
+        }
+        let chunk = match self.granularity() {
+            Granularity::Auto => self.auto_chunk(items.len()),
+            Granularity::Cell => 1,
+            Granularity::Rows(n) | Granularity::Tile(n, _) => n,
+        }
+        .max(1);
+        let f = Arc::new(f);
+        let mut receivers = Vec::new();
+        let mut items = items.into_iter().peekable();
+        let mut start = 0;
+        while items.peek().is_some() {
+            let batch = items.by_ref().take(chunk).collect::<Vec<_>>();
+            let len = batch.len();
+            let (tx, rx) = oneshot::channel::<MsgResult<U>>();
+            let f = f.clone();
+            let idx = receivers.len();
+            self.execute(move || {
+                let result = batch
+                    .into_iter()
+                    .enumerate()
+                    .map(|(offset, item)| {
+                        let row = start + offset;
+                        panic::catch_unwind(AssertUnwindSafe(|| f(item)))
+                            .map_err(|payload| {
+                                MatrixError::worker(row, 0, WorkerFailure::from_panic(payload))
+                            })?
+                            .map_err(|e| {
+                                MatrixError::worker(row, 0, WorkerFailure::Error(Box::new(e)))
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|values| MsgOutput { idx, values });
+                let _ = tx.send(result);
+            })?;
+            receivers.push((start, rx));
+            start += len;
+        }
+        let mut data = Vec::with_capacity(start);
+        for (start, rx) in receivers {
+            let output = rx
+                .blocking_recv()
+                .map_err(|_| MatrixError::worker(start, 0, WorkerFailure::Disconnected))??;
+            data.extend(output.values);
+        }
+        Ok(data)
+    }
+
+    /// Whether an elementwise operation over `len` elements is cheaper on
+    /// the calling thread than on the workers.
+    pub(crate) fn runs_locally(&self, len: usize) -> bool {
+        len <= self.sequential_threshold().max(ELEMENTWISE_CHUNK)
+    }
+}
+
+fn compute_chunk<U>(
+    range: Range<usize>,
+    cols: usize,
+    f: &(impl Fn(usize) -> Result<U, MatrixError> + Sync),
+) -> Result<Vec<U>, MatrixError> {
+    let mut values = Vec::with_capacity(range.len());
+    for idx in range {
+        let (i, j) = cell(idx, cols);
+        let value = panic::catch_unwind(AssertUnwindSafe(|| f(idx)))
+            .map_err(|payload| MatrixError::worker(i, j, WorkerFailure::from_panic(payload)))?
+            .map_err(|e| MatrixError::worker(i, j, WorkerFailure::Error(Box::new(e))))?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+fn cell(idx: usize, cols: usize) -> (usize, usize) {
+    let cols = cols.max(1);
+    (idx / cols, idx % cols)
+}
+
+/// A product whose tiles have been sent to the workers.
+struct PendingProduct<T> {
+    data: Vec<T>,
+    row: usize,
+    col: usize,
+    tiles: Vec<(Range<usize>, Range<usize>)>,
+    receivers: Vec<oneshot::Receiver<MsgResult<T>>>,
+}
+
+impl<T> PendingProduct<T> {
+    fn ready(matrix: Matrix<T>) -> Self {
+        Self {
+            data: matrix.data,
+            row: matrix.row,
+            col: matrix.col,
+            tiles: Vec::new(),
+            receivers: Vec::new(),
+        }
+    }
+
+    fn wait(mut self) -> Result<Matrix<T>, MatrixError> {
+        for (idx, rx) in std::mem::take(&mut self.receivers).into_iter().enumerate() {
+            let output = rx.blocking_recv().map_err(|_| self.disconnected(idx))??;
+            self.place(output);
+        }
+        Ok(self.into_matrix())
+    }
+
+    async fn wait_async(mut self) -> Result<Matrix<T>, MatrixError> {
+        for (idx, rx) in std::mem::take(&mut self.receivers).into_iter().enumerate() {
+            let output = rx.await.map_err(|_| self.disconnected(idx))??;
+            self.place(output);
+        }
+        Ok(self.into_matrix())
+    }
+
+    fn disconnected(&self, idx: usize) -> MatrixError {
+        let (rows, cols) = &self.tiles[idx];
+        MatrixError::worker(rows.start, cols.start, WorkerFailure::Disconnected)
+    }
+
+    fn place(&mut self, output: MsgOutput<T>) {
+        let (rows, cols) = &self.tiles[output.idx];
+        let mut values = output.values.into_iter();
+        for i in rows.clone() {
+            for (j, value) in cols.clone().zip(values.by_ref()) {
+                self.data[i * self.col + j] = value;
+            }
+        }
+    }
+
+    fn into_matrix(self) -> Matrix<T> {
+        Matrix {
+            data: self.data,
+            row: self.row,
+            col: self.col,
+            layout: Layout::RowMajor,
+        }
+    }
+}
+
+fn packed<T: Clone>(m: Arc<Matrix<T>>, layout: Layout) -> Arc<Matrix<T>> {
+    if m.layout == layout {
+        m
+    } else {
+        Arc::new(m.to_layout(layout))
+    }
+}
+
+fn check_multiply(a: (usize, usize), b: (usize, usize)) -> Result<(), MatrixError> {
+    if a.1 != b.0 {
+        return Err(MatrixError::DimensionMismatch { left: a, right: b });
+    }
+    Ok(())
+}
+
+fn multiply_sequential<T>(a: MatrixView<T>, b: MatrixView<T>) -> Result<Matrix<T>, MatrixError>
+where
+    T: Semiring,
+{
+    let mut data = Vec::with_capacity(a.rows() * b.cols());
+    for i in 0..a.rows() {
+        for j in 0..b.cols() {
+            data.push(dot(a.row(i).iter(), b.col(j).iter())?);
+        }
+    }
+    Ok(Matrix {
+        data,
+        row: a.rows(),
+        col: b.cols(),
+        layout: Layout::RowMajor,
+    })
+}
+
+impl<T> Matrix<T> {
+    /// Builds a `row x col` matrix from row-major `data`.
+    pub fn new(data: impl Into<Vec<T>>, row: usize, col: usize) -> Result<Self, MatrixError> {
+        let data = data.into();
+        if row.checked_mul(col) != Some(data.len()) {
+            return Err(MatrixError::InvalidShape {
+                len: data.len(),
+                rows: row,
+                cols: col,
+            });
+        }
+        Ok(Self {
+            data,
+            row,
+            col,
+            layout: Layout::RowMajor,
+        })
+    }
+
+    pub fn from_fn(row: usize, col: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut data = Vec::with_capacity(row * col);
+        for i in 0..row {
+            for j in 0..col {
+                data.push(f(i, j));
+            }
+        }
+        Self {
+            data,
+            row,
+            col,
+            layout: Layout::RowMajor,
+        }
+    }
+
+    /// Builds a matrix from its rows, which must all have the same length.
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Result<Self, MatrixError>
+    where
+        R: Into<Vec<T>>,
+    {
+        let mut data = Vec::new();
+        let mut row = 0;
+        let mut col = 0;
+        for r in rows {
+            let r = r.into();
+            if row == 0 {
+                col = r.len();
+            } else if r.len() != col {
+                return Err(MatrixError::RaggedRow {
+                    row,
+                    expected: col,
+                    found: r.len(),
+                });
+            }
+            data.extend(r);
+            row += 1;
+        }
+        Ok(Self {
+            data,
+            row,
+            col,
+            layout: Layout::RowMajor,
+        })
+    }
+
+    pub fn zeros(row: usize, col: usize) -> Self
+    where
+        T: Clone + Default,
+    {
+        Self {
+            data: vec![T::default(); row * col],
+            row,
+            col,
+            layout: Layout::RowMajor,
+        }
+    }
+
+    pub fn identity(n: usize) -> Self
+    where
+        T: Semiring,
+    {
+        Self::from_fn(n, n, |i, j| if i == j { T::one() } else { T::zero() })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.row
+    }
+
+    pub fn cols(&self) -> usize {
+        self.col
+    }
+
+    /// `(rows, cols)` of the matrix.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if i < self.row && j < self.col {
+            self.data.get(self.offset(i, j))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        if i < self.row && j < self.col {
+            let offset = self.offset(i, j);
+            self.data.get_mut(offset)
+        } else {
+            None
+        }
+    }
+
+    /// All elements in storage order, as given by `layout`.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = impl ExactSizeIterator<Item = &T>> {
+        (0..self.row).map(move |i| self.row(i).iter())
+    }
+
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.col).map(move |j| self.col(j).iter())
+    }
+
+    fn to_shared(&self) -> Arc<Matrix<T>>
+    where
+        T: Clone,
+    {
+        Arc::new(self.clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        self.get(i, j).unwrap_or_else(|| {
+            panic!(
+                "index ({}, {}) out of bounds for {}x{} matrix",
+                i, j, self.row, self.col
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        let (row, col) = (self.row, self.col);
+        self.get_mut(i, j).unwrap_or_else(|| {
+            panic!(
+                "index ({}, {}) out of bounds for {}x{} matrix",
+                i, j, row, col
+            )
+        })
+    }
+}
+
+impl<T> From<Matrix<T>> for Vec<Vec<T>> {
+    fn from(matrix: Matrix<T>) -> Self {
+        let matrix = match matrix.layout {
+            Layout::RowMajor => matrix,
+            // moves the elements out column by column, without `T: Clone`
+            Layout::ColMajor => {
+                let (row, col) = matrix.shape();
+                let mut cols = matrix.data.into_iter();
+                let mut rows = (0..row)
+                    .map(|_| Vec::with_capacity(col))
+                    .collect::<Vec<_>>();
+                for _ in 0..col {
+                    for row in rows.iter_mut() {
+                        row.extend(cols.next());
+                    }
+                }
+                return rows;
+            }
+        };
+        if matrix.col == 0 {
+            return (0..matrix.row).map(|_| Vec::new()).collect();
+        }
+        let mut data = matrix.data.into_iter();
+        (0..matrix.row)
+            .map(|_| data.by_ref().take(matrix.col).collect())
+            .collect()
+    }
+}
+
+impl<T> fmt::Display for Matrix<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.col == 0 {
+            // empty rows are only visible by their commas, so an `n x 0`
+            // matrix is written as `n` of them; `0 x n` falls through to `{}`
+            return write!(f, "{{{}}}", vec![","; self.row].join(" "));
+        }
+        write!(f, "{{")?;
+        for i in 0..self.row {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            for (j, value) in self.row(i).iter().enumerate() {
+                if j != 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", value)?;
+            }
+        }
+        write!(f, "}}")?;
+        Ok(())
+    }
+}
+
+impl<T> MsgInput<T> {
+    pub fn new(
+        idx: usize,
+        a: Arc<Matrix<T>>,
+        b: Arc<Matrix<T>>,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> Self {
+        Self {
+            idx,
+            a,
+            b,
+            rows,
+            cols,
+        }
+    }
+}
+
+impl<T> Msg<T> {
+    pub fn new(input: MsgInput<T>, sender: oneshot::Sender<MsgResult<T>>) -> Self {
+        Self { input, sender }
+    }
+}
+
+impl<T> Msg<T>
+where
+    T: Semiring,
+{
+    fn process(self) {
+        let result = compute_block(&self.input).map(|values| MsgOutput {
+            idx: self.input.idx,
+            values,
+        });
+        // the receiver is only gone if the caller stopped waiting for us
+        let _ = self.sender.send(result);
+    }
+}
+
+fn compute_block<T>(input: &MsgInput<T>) -> Result<Vec<T>, MatrixError>
+where
+    T: Semiring,
+{
+    let mut values = Vec::with_capacity(input.rows.len() * input.cols.len());
+    for i in input.rows.clone() {
+        for j in input.cols.clone() {
+            let value = panic::catch_unwind(AssertUnwindSafe(|| {
+                match (input.a.layout, input.b.layout) {
+                    (Layout::RowMajor, Layout::ColMajor) => {
+                        dot(input.a.lane(i).iter(), input.b.lane(j).iter())
+                    }
+                    _ => dot(input.a.row(i).iter(), input.b.col(j).iter()),
+                }
+            }))
+            .map_err(|payload| MatrixError::worker(i, j, WorkerFailure::from_panic(payload)))?
+            .map_err(|e| MatrixError::worker(i, j, WorkerFailure::Error(Box::new(e))))?;
+            values.push(value);
+        }
+    }
+    Ok(values)
+}
+
+impl<T> Mul for Matrix<T>
+where
+    T: Semiring,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        MatrixExecutor::global()
+            .multiply_shared(&Arc::new(self), &Arc::new(rhs))
+            .expect("Matrix multiply error")
+    }
+}
+
+#[test]
+fn test_matrix_display() -> anyhow::Result<()> {
+    let a = Matrix::new([1, 2, 3, 4], 2, 2)?;
+    let b = Matrix::new([1, 2, 3, 4], 2, 2)?;
+    let c = a * b;
+    assert_eq!(c.data, vec![7, 10, 15, 22]);
+    assert_eq!(format!("{}", c), "{7 10, 15 22}");
+    Ok(())
+}
+
+#[test]
+fn test_a_can_not_multiply_b() -> anyhow::Result<()> {
+    let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3)?;
+    let b = Matrix::new([1, 2, 3, 4], 2, 2)?;
+    let c = multiply(&a, &b);
+    assert!(matches!(
+        c,
+        Err(MatrixError::DimensionMismatch {
+            left: (2, 3),
+            right: (2, 2)
+        })
+    ));
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn test_a_can_not_multiply_b_panic() {
+    let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3).unwrap();
+    let b = Matrix::new([1, 2, 3, 4], 2, 2).unwrap();
+    let _c = a * b;
+}
+
+#[test]
+fn test_executor_is_reused_across_threads() -> anyhow::Result<()> {
+    let executor = std::sync::Arc::new(MatrixExecutor::new(3)?);
+    let handles = (0..4)
+        .map(|_| {
+            let executor = executor.clone();
+            std::thread::spawn(move || {
+                let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3)?;
+                let b = Matrix::new([1, 2, 3, 4, 5, 6], 3, 2)?;
+                (0..50)
+                    .map(|_| executor.multiply(&a, &b).map(|c| c.data))
+                    .collect::<Result<Vec<_>, MatrixError>>()
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        for data in handle.join().expect("thread panicked")? {
+            assert_eq!(data, vec![22, 28, 49, 64]);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_sequential_fallback_matches_workers() -> anyhow::Result<()> {
+    let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3)?;
+    let b = Matrix::new([1, 2, 3, 4, 5, 6], 3, 2)?;
+    let parallel = MatrixExecutor::builder().num_threads(2).build()?;
+    let sequential = MatrixExecutor::builder()
+        .num_threads(1)
+        .sequential_threshold(usize::MAX)
+        .build()?;
+    assert_eq!(
+        parallel.multiply(&a, &b)?.data,
+        sequential.multiply(&a, &b)?.data
+    );
+    Ok(())
+}
+
+#[test]
+fn test_multiply_granularity_keeps_layout() -> anyhow::Result<()> {
+    let a = Matrix::new((1..=15).collect::<Vec<i64>>(), 5, 3)?;
+    let b = Matrix::new((1..=12).collect::<Vec<i64>>(), 3, 4)?;
+    let expected = multiply(&a, &b)?.data;
+    for granularity in [
+        Granularity::Auto,
+        Granularity::Cell,
+        Granularity::Rows(2),
+        Granularity::Tile(2, 3),
+        Granularity::Tile(8, 8),
+    ] {
+        let executor = MatrixExecutor::builder()
+            .num_threads(2)
+            .granularity(granularity)
+            .build()?;
+        assert_eq!(executor.multiply(&a, &b)?.data, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_huge_granularity_covers_everything() -> anyhow::Result<()> {
+    let a = Matrix::new([1, 2, 3, 4], 2, 2)?;
+    let expected = multiply(&a, &a)?;
+    for granularity in [
+        Granularity::Rows(usize::MAX),
+        Granularity::Tile(usize::MAX, usize::MAX),
+    ] {
+        let executor = MatrixExecutor::builder()
+            .num_threads(2)
+            .granularity(granularity)
+            .build()?;
+        assert_eq!(executor.multiply(&a, &a)?, expected);
+        let product = executor.multiply_with(&a, &a, crate::Arithmetic::Checked)?;
+        assert_eq!(product, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_multiply_shared_operands() -> anyhow::Result<()> {
+    let a = Arc::new(Matrix::new([1, 2, 3, 4], 2, 2)?);
+    let b = Arc::new(Matrix::new([5, 6, 7, 8], 2, 2)?);
+    let executor = MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(Granularity::Rows(1))
+        .build()?;
+    let c = executor.multiply_shared(&a, &b)?;
+    assert_eq!(c.data, vec![19, 22, 43, 50]);
+    assert_eq!(a.data, vec![1, 2, 3, 4]);
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_multiply_async_inside_runtime() -> anyhow::Result<()> {
+    let a = Matrix::new([1, 2, 3, 4, 5, 6], 2, 3)?;
+    let b = Matrix::new([1, 2, 3, 4, 5, 6], 3, 2)?;
+    let c = multiply_async(&a, &b).await?;
+    assert_eq!(c.data, vec![22, 28, 49, 64]);
+
+    let executor = MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(Granularity::Rows(1))
+        .build()?;
+    let c = executor.multiply_async(&a, &b).await?;
+    assert_eq!(format!("{}", c), "{22 28, 49 64}");
+    Ok(())
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn test_worker_panic_reports_cell() -> anyhow::Result<()> {
+    let a = Matrix::new([1, 0, 1, i32::MAX], 2, 2)?;
+    let b = Matrix::new([1, 0, 0, 2], 2, 2)?;
+    let executor = MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(Granularity::Rows(1))
+        .build()?;
+    let err = anyhow::Error::from(
+        executor
+            .multiply(&a, &b)
+            .err()
+            .expect("multiply should fail"),
+    );
+    assert!(matches!(
+        err.downcast_ref::<MatrixError>(),
+        Some(MatrixError::WorkerFailed {
+            row: 1,
+            col: 1,
+            cause: WorkerFailure::Panic(_)
+        })
+    ));
+
+    // the worker survives the panic and keeps serving requests
+    let c = executor.multiply(&b, &b)?;
+    assert_eq!(c.data, vec![1, 0, 0, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_matrix_new_validates_shape() {
+    assert!(matches!(
+        Matrix::new([1, 2, 3], 2, 2),
+        Err(MatrixError::InvalidShape {
+            len: 3,
+            rows: 2,
+            cols: 2
+        })
+    ));
+    assert!(matches!(
+        Matrix::from_rows([vec![1, 2], vec![3]]),
+        Err(MatrixError::RaggedRow {
+            row: 1,
+            expected: 2,
+            found: 1
+        })
+    ));
+}
+
+#[test]
+fn test_matrix_constructors() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1, 2], [3, 4]])?;
+    assert_eq!(a.data, vec![1, 2, 3, 4]);
+    let b = Matrix::from_fn(2, 3, |i, j| i * 3 + j);
+    assert_eq!(b.data, vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(format!("{}", Matrix::<i32>::zeros(2, 2)), "{0 0, 0 0}");
+    let id = Matrix::<i32>::identity(2);
+    assert_eq!(multiply(&a, &id)?.data, a.data);
+    Ok(())
+}
+
+#[test]
+fn test_empty_matrices() -> anyhow::Result<()> {
+    let a = Matrix::<i32>::zeros(2, 0);
+    let b = Matrix::<i32>::zeros(0, 3);
+    let c = multiply(&a, &b)?;
+    assert_eq!((c.row, c.col), (2, 3));
+    assert_eq!(c.data, vec![0; 6]);
+    assert_eq!(format!("{}", a), "{, ,}");
+    assert_eq!(format!("{}", b), "{}");
+
+    let executor = MatrixExecutor::builder().num_threads(2).build()?;
+    let d = executor.multiply(&b, &Matrix::<i32>::zeros(3, 4))?;
+    assert_eq!((d.row, d.col), (0, 4));
+    Ok(())
+}
+
+#[test]
+fn test_matrix_element_access() -> anyhow::Result<()> {
+    let mut a = Matrix::from_rows([[1, 2, 3], [4, 5, 6]])?;
+    assert_eq!((a.rows(), a.cols(), a.shape()), (2, 3, (2, 3)));
+    assert_eq!(a[(1, 2)], 6);
+    assert_eq!(a.get(0, 3), None);
+    a[(0, 1)] = 20;
+    assert_eq!(a.get(0, 1), Some(&20));
+
+    let rows = a
+        .iter_rows()
+        .map(|row| row.copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(rows, vec![vec![1, 20, 3], vec![4, 5, 6]]);
+    let cols = a
+        .iter_cols()
+        .map(|col| col.copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(cols, vec![vec![1, 4], vec![20, 5], vec![3, 6]]);
+
+    let b = a.clone();
+    assert_eq!(a, b);
+    assert_eq!(
+        Vec::<Vec<i32>>::from(b),
+        vec![vec![1, 20, 3], vec![4, 5, 6]]
+    );
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_matrix_index_out_of_bounds() {
+    let a = Matrix::<i32>::zeros(2, 2);
+    let _ = a[(0, 2)];
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_validates_shape() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1, 2], [3, 4]])?;
+    let json = serde_json::to_string(&a)?;
+    assert_eq!(json, r#"{"data":[1,2,3,4],"rows":2,"cols":2}"#);
+    assert_eq!(serde_json::from_str::<Matrix<i32>>(&json)?, a);
+    let c = a.to_layout(Layout::ColMajor);
+    let json = serde_json::to_string(&c)?;
+    assert_eq!(
+        json,
+        r#"{"data":[1,3,2,4],"rows":2,"cols":2,"layout":"ColMajor"}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Matrix<i32>>(&json)?.layout(),
+        Layout::ColMajor
+    );
+    assert!(serde_json::from_str::<Matrix<i32>>(r#"{"data":[1,2,3],"rows":2,"cols":2}"#).is_err());
+    Ok(())
+}
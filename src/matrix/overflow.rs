@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use super::{check_multiply, Layout, Matrix, MatrixExecutor};
+use crate::{
+    error::{MatrixError, WorkerFailure},
+    semiring::{Arithmetic, Integer},
+    vector::dot_with,
+};
+
+/// Integer `multiply` with an explicit overflow mode.
+pub fn multiply_with<T: Integer>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    mode: Arithmetic,
+) -> Result<Matrix<T>, MatrixError> {
+    MatrixExecutor::global().multiply_with(a, b, mode)
+}
+
+impl MatrixExecutor {
+    pub fn multiply_with<T: Integer>(
+        &self,
+        a: &Matrix<T>,
+        b: &Matrix<T>,
+        mode: Arithmetic,
+    ) -> Result<Matrix<T>, MatrixError> {
+        check_multiply(a.shape(), b.shape())?;
+        let (row, col) = (a.row, b.col);
+        let a = Arc::new(a.to_layout(Layout::RowMajor));
+        let b = Arc::new(b.to_layout(Layout::ColMajor));
+        let data = self
+            .map_indexed(row * col, col, move |idx| {
+                let (i, j) = (idx / col, idx % col);
+                dot_with(a.lane(i).iter(), b.lane(j).iter(), mode)?
+                    .ok_or(MatrixError::Overflow { row: i, col: j })
+            })
+            .map_err(|e| match e {
+                // the overflow already names its cell
+                MatrixError::WorkerFailed {
+                    cause: WorkerFailure::Error(inner),
+                    ..
+                } if matches!(*inner, MatrixError::Overflow { .. }) => *inner,
+                e => e,
+            })?;
+        Ok(Matrix {
+            data,
+            row,
+            col,
+            layout: Layout::RowMajor,
+        })
+    }
+}
+
+#[test]
+fn test_multiply_overflow_modes() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1i64, 0], [i64::MAX, 1]])?;
+    let b = Matrix::from_rows([[1i64, 2], [1, 3]])?;
+    let executor = MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(crate::Granularity::Rows(1))
+        .build()?;
+    assert!(matches!(
+        executor.multiply_with(&a, &b, Arithmetic::Checked),
+        Err(MatrixError::Overflow { row: 1, col: 0 })
+    ));
+    assert_eq!(
+        executor.multiply_with(&a, &b, Arithmetic::Saturating)?,
+        Matrix::from_rows([[1, 2], [i64::MAX, i64::MAX]])?
+    );
+    assert_eq!(
+        multiply_with(&a, &b, Arithmetic::Wrapping)?,
+        Matrix::from_rows([[1, 2], [i64::MIN, 1]])?
+    );
+    Ok(())
+}
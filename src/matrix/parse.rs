@@ -0,0 +1,249 @@
+use std::{fmt, str::FromStr};
+
+use super::Matrix;
+use crate::error::MatrixError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Open,
+    Close,
+    Comma,
+    Newline,
+    Value(&'a str),
+}
+
+/// A token with its 1-based line and column.
+type Spanned<'a> = (Token<'a>, usize, usize);
+
+/// The values of one row with their positions.
+type Row<'a> = Vec<(&'a str, usize, usize)>;
+
+/// Parses either the `Display` format, `{1 2, 3 4}`, or one row per line
+/// with values separated by whitespace or commas. In the `Display` format an
+/// `n x 0` matrix is written as `n` commas, such as `{, ,}` for `2 x 0`, and
+/// `{}` is `0 x 0`. A `0 x n` matrix also displays as `{}`, so its width is
+/// lost and it parses back as `0 x 0`:
+///
+/// ```text
+/// 1, 2
+/// 3, 4
+/// ```
+impl<T> FromStr for Matrix<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Err = MatrixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s);
+        let rows = match tokens.iter().find(|(t, ..)| *t != Token::Newline) {
+            Some((Token::Open, ..)) => braced_rows(&tokens, s)?,
+            _ => line_rows(&tokens)?,
+        };
+        let mut values = Vec::with_capacity(rows.len());
+        let mut expected = None;
+        for row in rows {
+            let expected = *expected.get_or_insert(row.len());
+            if row.len() != expected {
+                let (_, line, column) = row[0];
+                return Err(parse_error(
+                    line,
+                    column,
+                    format!("row has {} values, expected {}", row.len(), expected),
+                ));
+            }
+            let row = row
+                .into_iter()
+                .map(|(value, line, column)| {
+                    value.parse::<T>().map_err(|e| {
+                        parse_error(line, column, format!("invalid value `{}`: {}", value, e))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            values.push(row);
+        }
+        Matrix::from_rows(values)
+    }
+}
+
+fn tokenize(s: &str) -> Vec<Spanned<'_>> {
+    let mut tokens = Vec::new();
+    let (mut line, mut column) = (1, 1);
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            '{' => Some(Token::Open),
+            '}' => Some(Token::Close),
+            ',' => Some(Token::Comma),
+            '\n' => Some(Token::Newline),
+            c if c.is_whitespace() => None,
+            _ => {
+                let mut end = start + c.len_utf8();
+                let mut width = 1;
+                while let Some(&(idx, c)) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '{' | '}' | ',') {
+                        break;
+                    }
+                    end = idx + c.len_utf8();
+                    width += 1;
+                    chars.next();
+                }
+                tokens.push((Token::Value(&s[start..end]), line, column));
+                column += width;
+                continue;
+            }
+        };
+        if let Some(token) = token {
+            tokens.push((token, line, column));
+        }
+        if c == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    tokens
+}
+
+/// Rows of the `{a b, c d}` format; newlines count as whitespace.
+fn braced_rows<'a>(tokens: &[Spanned<'a>], s: &str) -> Result<Vec<Row<'a>>, MatrixError> {
+    let mut tokens = tokens.iter().filter(|(t, ..)| *t != Token::Newline);
+    tokens.next();
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    // position of the first comma of an `n x 0` matrix
+    let mut empty_rows = None;
+    loop {
+        match tokens.next() {
+            Some(&(Token::Value(value), line, column)) => {
+                if let Some((line, column)) = empty_rows {
+                    return Err(parse_error(line, column, "empty row"));
+                }
+                row.push((value, line, column));
+            }
+            Some(&(Token::Comma, line, column)) => {
+                if !row.is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                } else if rows.is_empty() || empty_rows.is_some() {
+                    empty_rows.get_or_insert((line, column));
+                    rows.push(Vec::new());
+                } else {
+                    return Err(parse_error(line, column, "empty row"));
+                }
+            }
+            Some(&(Token::Close, line, column)) => {
+                if row.is_empty() && !rows.is_empty() && empty_rows.is_none() {
+                    return Err(parse_error(line, column, "empty row"));
+                }
+                if !row.is_empty() {
+                    rows.push(row);
+                }
+                break;
+            }
+            Some(&(Token::Open, line, column)) => {
+                return Err(parse_error(line, column, "unexpected `{`"));
+            }
+            Some((Token::Newline, ..)) => unreachable!("newlines are filtered out"),
+            None => {
+                let (line, column) = end_of(s);
+                return Err(parse_error(line, column, "missing closing `}`"));
+            }
+        }
+    }
+    if let Some(&(_, line, column)) = tokens.next() {
+        return Err(parse_error(line, column, "unexpected input after `}`"));
+    }
+    Ok(rows)
+}
+
+/// One row per non-empty line, values separated by whitespace or commas.
+fn line_rows<'a>(tokens: &[Spanned<'a>]) -> Result<Vec<Row<'a>>, MatrixError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    // a comma must sit between two values of the same line
+    let mut after_comma = false;
+    for &(token, line, column) in tokens {
+        match token {
+            Token::Value(value) => {
+                row.push((value, line, column));
+                after_comma = false;
+            }
+            Token::Comma if row.is_empty() || after_comma => {
+                return Err(parse_error(line, column, "missing value before `,`"));
+            }
+            Token::Comma => after_comma = true,
+            Token::Newline if after_comma => {
+                return Err(parse_error(line, column, "missing value after `,`"));
+            }
+            Token::Newline => {
+                if !row.is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                }
+            }
+            Token::Open => return Err(parse_error(line, column, "unexpected `{`")),
+            Token::Close => return Err(parse_error(line, column, "unexpected `}`")),
+        }
+    }
+    if let (true, Some(&(_, line, column))) = (after_comma, tokens.last()) {
+        return Err(parse_error(line, column, "missing value after `,`"));
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn end_of(s: &str) -> (usize, usize) {
+    let line = s.split('\n').count();
+    let column = s.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
+
+fn parse_error(line: usize, column: usize, message: impl Into<String>) -> MatrixError {
+    MatrixError::Parse {
+        line,
+        column,
+        message: message.into(),
+    }
+}
+
+#[test]
+fn test_parse_round_trips_display() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[7, 10], [15, 22]])?;
+    assert_eq!(a.to_string().parse::<Matrix<i32>>()?, a);
+    let b = Matrix::from_rows([[1.5, -2.0, 3.25]])?;
+    assert_eq!(b.to_string().parse::<Matrix<f64>>()?, b);
+    for (row, col) in [(0, 0), (1, 0), (2, 0)] {
+        let empty = Matrix::<i32>::zeros(row, col);
+        assert_eq!(
+            empty.to_string().parse::<Matrix<i32>>()?.shape(),
+            (row, col)
+        );
+    }
+    assert_eq!(Matrix::<i32>::zeros(2, 0).to_string(), "{, ,}");
+    // a matrix without rows has no text for its width
+    let empty = Matrix::<i32>::zeros(0, 3).to_string();
+    assert_eq!(empty, "{}");
+    assert_eq!(empty.parse::<Matrix<i32>>()?.shape(), (0, 0));
+    assert!("{, 1}".parse::<Matrix<i32>>().is_err());
+    assert_eq!(
+        "1, 2,3\n\n 4 5 6\n".parse::<Matrix<u8>>()?,
+        Matrix::from_rows([[1, 2, 3], [4, 5, 6]])?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_errors_report_position() {
+    let position = |s: &str| match s.parse::<Matrix<i32>>() {
+        Err(MatrixError::Parse { line, column, .. }) => Some((line, column)),
+        _ => None,
+    };
+    assert_eq!(position("{1 2, 3 x}"), Some((1, 9)));
+    assert_eq!(position("1 2\n3"), Some((2, 1)));
+    assert_eq!(position("1,,2"), Some((1, 3)));
+    assert_eq!(position("{1 2,\n 3 4"), Some((2, 5)));
+    assert_eq!(position("{1} 2"), Some((1, 5)));
+}
@@ -0,0 +1,289 @@
+use std::ops::Range;
+
+use super::{Matrix, MatrixExecutor, Real};
+use crate::{error::MatrixError, semiring::Semiring, vector::Vector};
+
+/// Elements folded into each partial result. Fixed, so that floating point
+/// sums do not depend on the executor's threads or granularity.
+const REDUCE_CHUNK: usize = 4096;
+
+impl MatrixExecutor {
+    /// Applies `f` to every element; the result keeps `a`'s layout.
+    pub fn map<T, U, F>(&self, a: &Matrix<T>, f: F) -> Result<Matrix<U>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Send + 'static,
+        F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        self.map_elements(a, f)
+    }
+
+    /// Combines corresponding elements of two matrices of the same shape.
+    pub fn zip_with<T, U, V, F>(
+        &self,
+        a: &Matrix<T>,
+        b: &Matrix<U>,
+        f: F,
+    ) -> Result<Matrix<V>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Copy + Send + Sync + 'static,
+        V: Send + 'static,
+        F: Fn(T, U) -> V + Send + Sync + 'static,
+    {
+        self.zip_elements(a, b, f)
+    }
+
+    pub fn sum<T: Semiring>(&self, a: &Matrix<T>) -> Result<T, MatrixError> {
+        let partials = self.fold_elements(a, T::zero(), |sum, x| sum.add(x))?;
+        Ok(partials.into_iter().fold(T::zero(), T::add))
+    }
+
+    /// The largest element, or `None` for an empty matrix. Incomparable
+    /// elements such as NaN are skipped.
+    pub fn max<T>(&self, a: &Matrix<T>) -> Result<Option<T>, MatrixError>
+    where
+        T: Copy + PartialOrd + Send + Sync + 'static,
+    {
+        let partials = self.fold_elements(a, None, larger)?;
+        Ok(partials.into_iter().flatten().fold(None, larger))
+    }
+
+    /// Sum of the diagonal of a square matrix.
+    pub fn trace<T: Semiring>(&self, a: &Matrix<T>) -> Result<T, MatrixError> {
+        if a.row != a.col {
+            return Err(MatrixError::NotSquare {
+                rows: a.row,
+                cols: a.col,
+            });
+        }
+        // Only `n` elements are read, fewer than it would take to share `a`
+        // with the workers, so the diagonal is summed here.
+        let partials = (0..a.row.div_ceil(REDUCE_CHUNK))
+            .map(|chunk| chunk_range(chunk, a.row).fold(T::zero(), |sum, k| sum.add(a[(k, k)])));
+        Ok(partials.fold(T::zero(), T::add))
+    }
+
+    /// Square root of the sum of squared elements.
+    pub fn frobenius_norm<T: Real>(&self, a: &Matrix<T>) -> Result<T, MatrixError> {
+        let partials = self.fold_elements(a, T::zero(), |sum, x| sum + x * x)?;
+        Ok(partials
+            .into_iter()
+            .fold(T::zero(), |sum, x| sum + x)
+            .sqrt())
+    }
+
+    /// The largest absolute row sum.
+    pub fn infinity_norm<T: Real>(&self, a: &Matrix<T>) -> Result<T, MatrixError> {
+        let sums = self.fold_rows(a, T::zero(), |sum, x| sum + x.abs())?;
+        Ok(sums
+            .iter()
+            .fold(T::zero(), |max, &x| if x > max { x } else { max }))
+    }
+
+    /// Folds each row, left to right, into one value per row.
+    pub fn fold_rows<T, U, F>(&self, a: &Matrix<T>, init: U, f: F) -> Result<Vector<U>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Clone + Send + Sync + 'static,
+        F: Fn(U, T) -> U + Send + Sync + 'static,
+    {
+        let (row, col) = a.shape();
+        let data = self.partials(a, row, row * col, move |a, i| {
+            a.row(i).iter().fold(init.clone(), |acc, &x| f(acc, x))
+        })?;
+        Ok(Vector::new(data))
+    }
+
+    /// Folds each column, top to bottom, into one value per column.
+    pub fn fold_cols<T, U, F>(&self, a: &Matrix<T>, init: U, f: F) -> Result<Vector<U>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Clone + Send + Sync + 'static,
+        F: Fn(U, T) -> U + Send + Sync + 'static,
+    {
+        let (row, col) = a.shape();
+        let data = self.partials(a, col, row * col, move |a, j| {
+            a.col(j).iter().fold(init.clone(), |acc, &x| f(acc, x))
+        })?;
+        Ok(Vector::new(data))
+    }
+
+    pub fn row_sums<T: Semiring>(&self, a: &Matrix<T>) -> Result<Vector<T>, MatrixError> {
+        self.fold_rows(a, T::zero(), T::add)
+    }
+
+    pub fn col_sums<T: Semiring>(&self, a: &Matrix<T>) -> Result<Vector<T>, MatrixError> {
+        self.fold_cols(a, T::zero(), T::add)
+    }
+
+    /// Folds the elements in row-major order, one partial result per
+    /// `REDUCE_CHUNK` elements.
+    fn fold_elements<T, U, F>(&self, a: &Matrix<T>, init: U, f: F) -> Result<Vec<U>, MatrixError>
+    where
+        T: Copy + Send + Sync + 'static,
+        U: Clone + Send + Sync + 'static,
+        F: Fn(U, T) -> U + Send + Sync + 'static,
+    {
+        let (row, col) = a.shape();
+        let len = row * col;
+        self.partials(a, len.div_ceil(REDUCE_CHUNK), len, move |a, chunk| {
+            chunk_range(chunk, len).fold(init.clone(), |acc, idx| f(acc, a[(idx / col, idx % col)]))
+        })
+    }
+
+    /// Returns `f(a, idx)` for every `idx < len`, in order. Small inputs are
+    /// folded on the calling thread; otherwise the workers share one copy of
+    /// `a`, and the granularity decides how many indices go in a message.
+    fn partials<T, U, F>(
+        &self,
+        a: &Matrix<T>,
+        len: usize,
+        work: usize,
+        f: F,
+    ) -> Result<Vec<U>, MatrixError>
+    where
+        T: Clone + Send + Sync + 'static,
+        U: Send + 'static,
+        F: Fn(&Matrix<T>, usize) -> U + Send + Sync + 'static,
+    {
+        if self.runs_locally(work) {
+            return Ok((0..len).map(|idx| f(a, idx)).collect());
+        }
+        let a = a.to_shared();
+        self.map_owned((0..len).collect(), work, move |idx| Ok(f(&a, idx)))
+    }
+}
+
+/// The `chunk`th `REDUCE_CHUNK`-sized range of `0..len`.
+fn chunk_range(chunk: usize, len: usize) -> Range<usize> {
+    let start = chunk * REDUCE_CHUNK;
+    start..(start + REDUCE_CHUNK).min(len)
+}
+
+fn larger<T: PartialOrd>(max: Option<T>, x: T) -> Option<T> {
+    match max {
+        // NaN is unordered even against itself, and is never kept
+        _ if x.partial_cmp(&x).is_none() => max,
+        Some(max) if max >= x => Some(max),
+        _ => Some(x),
+    }
+}
+
+impl<T> Matrix<T>
+where
+    T: Copy + Send + Sync + 'static,
+{
+    pub fn map<U, F>(&self, f: F) -> Result<Matrix<U>, MatrixError>
+    where
+        U: Send + 'static,
+        F: Fn(T) -> U + Send + Sync + 'static,
+    {
+        MatrixExecutor::global().map(self, f)
+    }
+
+    pub fn zip_with<U, V, F>(&self, other: &Matrix<U>, f: F) -> Result<Matrix<V>, MatrixError>
+    where
+        U: Copy + Send + Sync + 'static,
+        V: Send + 'static,
+        F: Fn(T, U) -> V + Send + Sync + 'static,
+    {
+        MatrixExecutor::global().zip_with(self, other, f)
+    }
+
+    pub fn max(&self) -> Result<Option<T>, MatrixError>
+    where
+        T: PartialOrd,
+    {
+        MatrixExecutor::global().max(self)
+    }
+}
+
+impl<T: Semiring> Matrix<T> {
+    pub fn sum(&self) -> Result<T, MatrixError> {
+        MatrixExecutor::global().sum(self)
+    }
+
+    pub fn trace(&self) -> Result<T, MatrixError> {
+        MatrixExecutor::global().trace(self)
+    }
+
+    pub fn row_sums(&self) -> Result<Vector<T>, MatrixError> {
+        MatrixExecutor::global().row_sums(self)
+    }
+
+    pub fn col_sums(&self) -> Result<Vector<T>, MatrixError> {
+        MatrixExecutor::global().col_sums(self)
+    }
+}
+
+impl<T: Real> Matrix<T> {
+    pub fn frobenius_norm(&self) -> Result<T, MatrixError> {
+        MatrixExecutor::global().frobenius_norm(self)
+    }
+
+    pub fn infinity_norm(&self) -> Result<T, MatrixError> {
+        MatrixExecutor::global().infinity_norm(self)
+    }
+}
+
+#[test]
+fn test_map_zip_and_reductions() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1, -7, 3], [4, 5, 6]])?;
+    assert_eq!(a.map(|x| x as f64 / 2.0)?[(0, 1)], -3.5);
+    assert_eq!(
+        a.map(|x| x + 10)?,
+        Matrix::from_rows([[11, 3, 13], [14, 15, 16]])?
+    );
+    assert_eq!(a.zip_with(&a, |x, y| x * y)?, crate::hadamard(&a, &a)?);
+    assert_eq!(a.sum()?, 12);
+    assert_eq!(a.max()?, Some(6));
+    assert_eq!(*a.row_sums()?, vec![-3, 15]);
+    assert_eq!(*a.col_sums()?, vec![5, -2, 9]);
+    assert!(matches!(a.trace(), Err(MatrixError::NotSquare { .. })));
+    assert_eq!(Matrix::from_rows([[1, 2], [3, 4]])?.trace()?, 5);
+    assert_eq!(Matrix::<i32>::zeros(0, 0).max()?, None);
+
+    let f = Matrix::from_rows([[3.0, -4.0], [f64::NAN, 1.0]])?;
+    assert_eq!(f.max()?, Some(3.0));
+    let f = Matrix::from_rows([[3.0, -4.0], [0.0, 1.0]])?;
+    assert_eq!(f.frobenius_norm()?, 26f64.sqrt());
+    assert_eq!(f.infinity_norm()?, 7.0);
+    Ok(())
+}
+
+#[test]
+fn test_float_sum_is_deterministic() -> anyhow::Result<()> {
+    let a = Matrix::from_fn(297, 303, |i, j| {
+        if (i + j) % 11 == 0 {
+            1e16
+        } else {
+            0.1 * (i as f64 - j as f64)
+        }
+    });
+    let sequential = MatrixExecutor::builder()
+        .num_threads(1)
+        .sequential_threshold(usize::MAX)
+        .build()?
+        .sum(&a)?;
+    for (threads, granularity) in [
+        (2, crate::Granularity::Cell),
+        (3, crate::Granularity::Rows(2)),
+    ] {
+        let executor = MatrixExecutor::builder()
+            .num_threads(threads)
+            .granularity(granularity)
+            .build()?;
+        assert_eq!(executor.sum(&a)?.to_bits(), sequential.to_bits());
+        let c = a.to_layout(crate::Layout::ColMajor);
+        assert_eq!(executor.sum(&c)?.to_bits(), sequential.to_bits());
+        let on_worker = executor.fold_rows(&a, true, |on_worker, _| {
+            on_worker
+                && std::thread::current()
+                    .name()
+                    .is_some_and(|name| name.starts_with("matrix-worker"))
+        })?;
+        assert!(on_worker.iter().all(|&on_worker| on_worker));
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use std::ops::{Index, IndexMut, Mul};
+
+use super::{Layout, Matrix};
+use crate::{error::MatrixError, semiring::Semiring};
+
+/// An `R x C` matrix whose size is part of its type, stored inline. Products
+/// only compile when the inner dimensions agree, and are computed on the
+/// calling thread.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SMatrix<T, const R: usize, const C: usize> {
+    data: [[T; C]; R],
+}
+
+impl<T, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub const fn new(data: [[T; C]; R]) -> Self {
+        Self { data }
+    }
+
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> T) -> Self {
+        Self {
+            data: std::array::from_fn(|i| std::array::from_fn(|j| f(i, j))),
+        }
+    }
+
+    pub fn zeros() -> Self
+    where
+        T: Semiring,
+    {
+        Self::from_fn(|_, _| T::zero())
+    }
+
+    pub const fn shape(&self) -> (usize, usize) {
+        (R, C)
+    }
+
+    pub fn as_array(&self) -> &[[T; C]; R] {
+        &self.data
+    }
+
+    pub fn transpose(&self) -> SMatrix<T, C, R>
+    where
+        T: Copy,
+    {
+        SMatrix::from_fn(|i, j| self.data[j][i])
+    }
+}
+
+impl<T: Semiring, const N: usize> SMatrix<T, N, N> {
+    pub fn identity() -> Self {
+        Self::from_fn(|i, j| if i == j { T::one() } else { T::zero() })
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for SMatrix<T, R, C> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[i][j]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for SMatrix<T, R, C> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.data[i][j]
+    }
+}
+
+impl<T, const R: usize, const K: usize, const C: usize> Mul<SMatrix<T, K, C>> for SMatrix<T, R, K>
+where
+    T: Semiring,
+{
+    type Output = SMatrix<T, R, C>;
+    fn mul(self, rhs: SMatrix<T, K, C>) -> Self::Output {
+        SMatrix::from_fn(|i, j| {
+            (0..K).fold(T::zero(), |sum, k| {
+                sum.add(self.data[i][k].mul(rhs.data[k][j]))
+            })
+        })
+    }
+}
+
+impl<T: Copy, const R: usize, const C: usize> TryFrom<&Matrix<T>> for SMatrix<T, R, C> {
+    type Error = MatrixError;
+
+    fn try_from(m: &Matrix<T>) -> Result<Self, Self::Error> {
+        if m.shape() != (R, C) {
+            return Err(MatrixError::DimensionMismatch {
+                left: m.shape(),
+                right: (R, C),
+            });
+        }
+        Ok(Self::from_fn(|i, j| m[(i, j)]))
+    }
+}
+
+impl<T: Copy, const R: usize, const C: usize> TryFrom<Matrix<T>> for SMatrix<T, R, C> {
+    type Error = MatrixError;
+
+    fn try_from(m: Matrix<T>) -> Result<Self, Self::Error> {
+        Self::try_from(&m)
+    }
+}
+
+impl<T, const R: usize, const C: usize> From<SMatrix<T, R, C>> for Matrix<T> {
+    fn from(m: SMatrix<T, R, C>) -> Self {
+        Matrix {
+            data: m.data.into_iter().flatten().collect(),
+            row: R,
+            col: C,
+            layout: Layout::RowMajor,
+        }
+    }
+}
+
+#[test]
+fn test_smatrix_multiply_and_convert() -> anyhow::Result<()> {
+    let a = SMatrix::new([[1, 2, 3], [4, 5, 6]]);
+    let b = SMatrix::new([[1, 0], [0, 1], [1, 1]]);
+    let c: SMatrix<i32, 2, 2> = a * b;
+    assert_eq!(c, SMatrix::new([[4, 5], [10, 11]]));
+    assert_eq!(c * SMatrix::identity(), c);
+    assert_eq!(a.transpose()[(2, 1)], 6);
+
+    let dynamic = Matrix::from(a);
+    assert_eq!(
+        super::multiply(&dynamic, &Matrix::from(b))?,
+        Matrix::from(c)
+    );
+    assert_eq!(SMatrix::<i32, 2, 3>::try_from(&dynamic)?, a);
+    assert!(matches!(
+        SMatrix::<i32, 3, 2>::try_from(dynamic),
+        Err(MatrixError::DimensionMismatch {
+            left: (2, 3),
+            right: (3, 2)
+        })
+    ));
+    Ok(())
+}
@@ -0,0 +1,464 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    ops::Mul,
+    sync::Arc,
+};
+
+use super::{Layout, Matrix, MatrixExecutor};
+use crate::{error::MatrixError, semiring::Semiring, vector::Vector};
+
+/// A sparse matrix in compressed sparse row (CSR) form: the entries of row
+/// `i` are `values[row_ptr[i]..row_ptr[i + 1]]`, in the columns given by the
+/// same range of `col_idx`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix<T> {
+    row: usize,
+    col: usize,
+    row_ptr: Vec<usize>,
+    col_idx: Vec<usize>,
+    values: Vec<T>,
+}
+
+pub fn sparse_multiply_dense<T>(
+    a: &SparseMatrix<T>,
+    b: &Matrix<T>,
+) -> Result<Matrix<T>, MatrixError>
+where
+    T: Semiring,
+{
+    MatrixExecutor::global().sparse_multiply_dense(a, b)
+}
+
+pub fn sparse_multiply_vector<T>(
+    a: &SparseMatrix<T>,
+    v: &Vector<T>,
+) -> Result<Vector<T>, MatrixError>
+where
+    T: Semiring,
+{
+    MatrixExecutor::global().sparse_multiply_vector(a, v)
+}
+
+pub fn sparse_multiply<T>(
+    a: &SparseMatrix<T>,
+    b: &SparseMatrix<T>,
+) -> Result<SparseMatrix<T>, MatrixError>
+where
+    T: Semiring + PartialEq,
+{
+    MatrixExecutor::global().sparse_multiply(a, b)
+}
+
+impl<T> SparseMatrix<T> {
+    pub fn new(
+        row: usize,
+        col: usize,
+        row_ptr: Vec<usize>,
+        col_idx: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, MatrixError> {
+        if row_ptr.len() != row + 1 || row_ptr[0] != 0 {
+            return Err(MatrixError::InvalidSparse(
+                "row_ptr must have rows + 1 offsets from 0",
+            ));
+        }
+        if row_ptr.windows(2).any(|w| w[0] > w[1]) {
+            return Err(MatrixError::InvalidSparse("row_ptr must not decrease"));
+        }
+        if col_idx.len() != values.len() || row_ptr[row] != values.len() {
+            return Err(MatrixError::InvalidSparse(
+                "row_ptr, col_idx and values disagree on length",
+            ));
+        }
+        if col_idx.iter().any(|&j| j >= col) {
+            return Err(MatrixError::InvalidSparse("column index out of bounds"));
+        }
+        // `get` binary-searches each row
+        if (0..row).any(|i| {
+            col_idx[row_ptr[i]..row_ptr[i + 1]]
+                .windows(2)
+                .any(|w| w[0] >= w[1])
+        }) {
+            return Err(MatrixError::InvalidSparse(
+                "column indices must strictly increase within a row",
+            ));
+        }
+        Ok(Self {
+            row,
+            col,
+            row_ptr,
+            col_idx,
+            values,
+        })
+    }
+
+    /// Builds a matrix from `(row, col, value)` entries; duplicates are summed.
+    pub fn from_triplets(
+        row: usize,
+        col: usize,
+        triplets: impl IntoIterator<Item = (usize, usize, T)>,
+    ) -> Result<Self, MatrixError>
+    where
+        T: Semiring,
+    {
+        let mut entries = BTreeMap::new();
+        for (i, j, value) in triplets {
+            if i >= row || j >= col {
+                return Err(MatrixError::InvalidSparse("entry out of bounds"));
+            }
+            match entries.entry((i, j)) {
+                Entry::Vacant(e) => {
+                    e.insert(value);
+                }
+                Entry::Occupied(mut e) => *e.get_mut() = e.get().add(value),
+            }
+        }
+        let mut row_ptr = vec![0; row + 1];
+        let mut col_idx = Vec::with_capacity(entries.len());
+        let mut values = Vec::with_capacity(entries.len());
+        for ((i, j), value) in entries {
+            row_ptr[i + 1] += 1;
+            col_idx.push(j);
+            values.push(value);
+        }
+        for i in 0..row {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+        Ok(Self {
+            row,
+            col,
+            row_ptr,
+            col_idx,
+            values,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.row
+    }
+
+    pub fn cols(&self) -> usize {
+        self.col
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// Number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The stored entry at `(i, j)`, if any.
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if i >= self.row {
+            return None;
+        }
+        let range = self.row_ptr[i]..self.row_ptr[i + 1];
+        self.col_idx[range.clone()]
+            .binary_search(&j)
+            .ok()
+            .map(|k| &self.values[range.start + k])
+    }
+
+    /// The stored entries of row `i` as `(col, value)` pairs.
+    pub fn row_entries(&self, i: usize) -> impl Iterator<Item = (usize, &T)> {
+        let range = self.row_ptr[i]..self.row_ptr[i + 1];
+        self.col_idx[range.clone()]
+            .iter()
+            .copied()
+            .zip(&self.values[range])
+    }
+
+    pub fn to_dense(&self) -> Matrix<T>
+    where
+        T: Semiring,
+    {
+        let mut dense = Matrix::from_fn(self.row, self.col, |_, _| T::zero());
+        for i in 0..self.row {
+            for (j, &value) in self.row_entries(i) {
+                dense.data[i * self.col + j] = value;
+            }
+        }
+        dense
+    }
+}
+
+impl<T> From<&Matrix<T>> for SparseMatrix<T>
+where
+    T: Semiring + PartialEq,
+{
+    fn from(dense: &Matrix<T>) -> Self {
+        let zero = T::zero();
+        let mut row_ptr = Vec::with_capacity(dense.row + 1);
+        let mut col_idx = Vec::new();
+        let mut values = Vec::new();
+        row_ptr.push(0);
+        for row in dense.iter_rows() {
+            for (j, &value) in row.enumerate() {
+                if value != zero {
+                    col_idx.push(j);
+                    values.push(value);
+                }
+            }
+            row_ptr.push(values.len());
+        }
+        Self {
+            row: dense.row,
+            col: dense.col,
+            row_ptr,
+            col_idx,
+            values,
+        }
+    }
+}
+
+impl<T> From<&SparseMatrix<T>> for Matrix<T>
+where
+    T: Semiring,
+{
+    fn from(sparse: &SparseMatrix<T>) -> Self {
+        sparse.to_dense()
+    }
+}
+
+impl MatrixExecutor {
+    pub fn sparse_multiply_dense<T>(
+        &self,
+        a: &SparseMatrix<T>,
+        b: &Matrix<T>,
+    ) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        self.sparse_multiply_dense_shared(&Arc::new(a.clone()), &b.to_shared())
+    }
+
+    /// Like `sparse_multiply_dense`, but hands the workers the caller's
+    /// operands instead of copying them once per call.
+    pub fn sparse_multiply_dense_shared<T>(
+        &self,
+        a: &Arc<SparseMatrix<T>>,
+        b: &Arc<Matrix<T>>,
+    ) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        if a.col != b.row {
+            return Err(MatrixError::DimensionMismatch {
+                left: a.shape(),
+                right: b.shape(),
+            });
+        }
+        let (row, col) = (a.row, b.col);
+        let (a, b) = (a.clone(), b.clone());
+        let data = self.map_indexed(row * col, col, move |idx| {
+            let (i, j) = (idx / col, idx % col);
+            let mut sum = T::zero();
+            for (k, &value) in a.row_entries(i) {
+                sum = sum.add(value.mul(b[(k, j)]));
+            }
+            Ok(sum)
+        })?;
+        Ok(Matrix {
+            data,
+            row,
+            col,
+            layout: Layout::RowMajor,
+        })
+    }
+
+    pub fn sparse_multiply_vector<T>(
+        &self,
+        a: &SparseMatrix<T>,
+        v: &Vector<T>,
+    ) -> Result<Vector<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        self.sparse_multiply_vector_shared(&Arc::new(a.clone()), &Arc::new(v.clone()))
+    }
+
+    /// Like `sparse_multiply_vector`, but hands the workers the caller's
+    /// operands instead of copying them once per call.
+    pub fn sparse_multiply_vector_shared<T>(
+        &self,
+        a: &Arc<SparseMatrix<T>>,
+        v: &Arc<Vector<T>>,
+    ) -> Result<Vector<T>, MatrixError>
+    where
+        T: Semiring,
+    {
+        if a.col != v.len() {
+            return Err(MatrixError::DimensionMismatch {
+                left: a.shape(),
+                right: (v.len(), 1),
+            });
+        }
+        let (a, v) = (a.clone(), v.clone());
+        let data = self.map_indexed(a.row, 1, move |i| {
+            let mut sum = T::zero();
+            for (k, &value) in a.row_entries(i) {
+                sum = sum.add(value.mul(v[k]));
+            }
+            Ok(sum)
+        })?;
+        Ok(Vector::new(data))
+    }
+
+    /// Entries of the product that sum to zero are not stored.
+    pub fn sparse_multiply<T>(
+        &self,
+        a: &SparseMatrix<T>,
+        b: &SparseMatrix<T>,
+    ) -> Result<SparseMatrix<T>, MatrixError>
+    where
+        T: Semiring + PartialEq,
+    {
+        self.sparse_multiply_shared(&Arc::new(a.clone()), &Arc::new(b.clone()))
+    }
+
+    /// Like `sparse_multiply`, but hands the workers the caller's operands
+    /// instead of copying them once per call.
+    pub fn sparse_multiply_shared<T>(
+        &self,
+        a: &Arc<SparseMatrix<T>>,
+        b: &Arc<SparseMatrix<T>>,
+    ) -> Result<SparseMatrix<T>, MatrixError>
+    where
+        T: Semiring + PartialEq,
+    {
+        if a.col != b.row {
+            return Err(MatrixError::DimensionMismatch {
+                left: a.shape(),
+                right: b.shape(),
+            });
+        }
+        let (row, col) = (a.row, b.col);
+        let (a, b) = (a.clone(), b.clone());
+        // each worker message produces whole rows of the product
+        let rows = self.map_indexed(row, 1, move |i| {
+            let mut acc = BTreeMap::new();
+            for (k, &x) in a.row_entries(i) {
+                for (j, &y) in b.row_entries(k) {
+                    let sum = acc.entry(j).or_insert_with(T::zero);
+                    *sum = sum.add(x.mul(y));
+                }
+            }
+            Ok(acc)
+        })?;
+        let zero = T::zero();
+        let mut row_ptr = Vec::with_capacity(row + 1);
+        let mut col_idx = Vec::new();
+        let mut values = Vec::new();
+        row_ptr.push(0);
+        for acc in rows {
+            for (j, value) in acc.into_iter().filter(|&(_, value)| value != zero) {
+                col_idx.push(j);
+                values.push(value);
+            }
+            row_ptr.push(values.len());
+        }
+        Ok(SparseMatrix {
+            row,
+            col,
+            row_ptr,
+            col_idx,
+            values,
+        })
+    }
+}
+
+impl<T> Mul<&Matrix<T>> for &SparseMatrix<T>
+where
+    T: Semiring,
+{
+    type Output = Matrix<T>;
+    fn mul(self, rhs: &Matrix<T>) -> Self::Output {
+        sparse_multiply_dense(self, rhs).expect("Sparse matrix multiply error")
+    }
+}
+
+impl<T> Mul<&Vector<T>> for &SparseMatrix<T>
+where
+    T: Semiring,
+{
+    type Output = Vector<T>;
+    fn mul(self, rhs: &Vector<T>) -> Self::Output {
+        sparse_multiply_vector(self, rhs).expect("Sparse matrix vector multiply error")
+    }
+}
+
+impl<T> Mul for &SparseMatrix<T>
+where
+    T: Semiring + PartialEq,
+{
+    type Output = SparseMatrix<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        sparse_multiply(self, rhs).expect("Sparse matrix multiply error")
+    }
+}
+
+#[test]
+fn test_sparse_dense_conversions() -> anyhow::Result<()> {
+    let dense = Matrix::from_rows([[0, 2, 0], [0, 0, 0], [1, 0, 3]])?;
+    let sparse = SparseMatrix::from(&dense);
+    assert_eq!(sparse.nnz(), 3);
+    assert_eq!(sparse.get(2, 2), Some(&3));
+    assert_eq!(sparse.get(1, 1), None);
+    assert_eq!(sparse.to_dense(), dense);
+    assert_eq!(
+        SparseMatrix::from_triplets(3, 3, [(2, 2, 1), (0, 1, 2), (2, 0, 1), (2, 2, 2)])?,
+        sparse
+    );
+    assert!(SparseMatrix::new(2, 2, vec![0, 1], vec![0], vec![1]).is_err());
+    assert!(SparseMatrix::new(1, 3, vec![0, 2], vec![2, 0], vec![5, 7]).is_err());
+    assert!(SparseMatrix::new(1, 3, vec![0, 2], vec![1, 1], vec![5, 7]).is_err());
+    assert!(SparseMatrix::new(2, 3, vec![0, 1, 2], vec![2, 0], vec![5, 7]).is_ok());
+    assert!(SparseMatrix::<i32>::from_triplets(2, 2, [(2, 0, 1)]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_sparse_products_match_dense() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[0, 2, 0], [0, 0, 0], [1, 0, 3]])?;
+    let b = Matrix::from_rows([[1, 0], [0, 4], [5, 0]])?;
+    let executor = MatrixExecutor::builder()
+        .num_threads(2)
+        .granularity(crate::Granularity::Rows(1))
+        .build()?;
+    let sa = SparseMatrix::from(&a);
+    let expected = super::multiply(&a, &b)?;
+    assert_eq!(executor.sparse_multiply_dense(&sa, &b)?, expected);
+    assert_eq!(
+        executor
+            .sparse_multiply(&sa, &SparseMatrix::from(&b))?
+            .to_dense(),
+        expected
+    );
+    assert_eq!(*(&sa * &Vector::new([1, 1, 1])), vec![2, 0, 4]);
+
+    let (sa, sb) = (Arc::new(sa), Arc::new(SparseMatrix::from(&b)));
+    assert_eq!(
+        executor.sparse_multiply_dense_shared(&sa, &Arc::new(b))?,
+        expected
+    );
+    assert_eq!(
+        executor.sparse_multiply_shared(&sa, &sb)?,
+        SparseMatrix::from(&expected)
+    );
+    let v = Arc::new(Vector::new([1, 1, 1]));
+    assert_eq!(
+        *executor.sparse_multiply_vector_shared(&sa, &v)?,
+        vec![2, 0, 4]
+    );
+
+    // entries that cancel out are not stored
+    let row = SparseMatrix::from(&Matrix::from_rows([[1, 1]])?);
+    let col = SparseMatrix::from(&Matrix::from_rows([[1], [-1]])?);
+    let product = &row * &col;
+    assert_eq!(product.nnz(), 0);
+    assert_eq!(product, SparseMatrix::from(&Matrix::from_rows([[0]])?));
+    Ok(())
+}
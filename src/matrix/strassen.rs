@@ -0,0 +1,167 @@
+use std::{ops::Sub, sync::Arc};
+
+use super::{check_multiply, Layout, Matrix, MatrixExecutor};
+use crate::{error::MatrixError, semiring::Semiring};
+
+/// Multiplies with Strassen's algorithm when both operands are square and
+/// larger than the executor's crossover, otherwise like `multiply`.
+pub fn multiply_strassen<T>(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+where
+    T: Semiring + Sub<Output = T>,
+{
+    MatrixExecutor::global().multiply_strassen(a, b)
+}
+
+/// How each of Strassen's seven products contributes to the output blocks
+/// `[c11, c12, c21, c22]`.
+const SIGNS: [[i8; 4]; 7] = [
+    [1, 0, 0, 1],
+    [0, 0, 1, -1],
+    [0, 1, 0, 1],
+    [1, 0, 1, 0],
+    [-1, 1, 0, 0],
+    [0, 0, 0, 1],
+    [1, 0, 0, 0],
+];
+
+impl MatrixExecutor {
+    pub fn multiply_strassen<T>(
+        &self,
+        a: &Matrix<T>,
+        b: &Matrix<T>,
+    ) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring + Sub<Output = T>,
+    {
+        check_multiply(a.shape(), b.shape())?;
+        if a.row != a.col || b.row != b.col || a.row <= self.strassen_crossover() {
+            return self.multiply(a, b);
+        }
+        self.strassen(a, b)
+    }
+
+    /// One level of the recursion. Only this level's seven products are in
+    /// flight at once: at the crossover they are sent to the workers
+    /// together, above it each is computed recursively in turn. Products are
+    /// added into the output blocks as they arrive.
+    fn strassen<T>(&self, a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>
+    where
+        T: Semiring + Sub<Output = T>,
+    {
+        let n = a.row;
+        // odd sizes are padded with a zero row and column
+        let h = n.div_ceil(2);
+        let [a11, a12, a21, a22] = a.quadrants(h);
+        let [b11, b12, b21, b22] = b.quadrants(h);
+        let operands = |k: usize| match k {
+            0 => (sum(&a11, &a22), sum(&b11, &b22)),
+            1 => (sum(&a21, &a22), b11.clone()),
+            2 => (a11.clone(), diff(&b12, &b22)),
+            3 => (a22.clone(), diff(&b21, &b11)),
+            4 => (sum(&a11, &a12), b22.clone()),
+            5 => (diff(&a21, &a11), sum(&b11, &b12)),
+            _ => (diff(&a12, &a22), sum(&b21, &b22)),
+        };
+        let mut blocks: [Matrix<T>; 4] =
+            std::array::from_fn(|_| Matrix::from_fn(h, h, |_, _| T::zero()));
+        let mut combine = |k: usize, m: Matrix<T>| {
+            for (block, sign) in blocks.iter_mut().zip(SIGNS[k]) {
+                match sign {
+                    1 => zip_in_place(block, &m, T::add),
+                    -1 => zip_in_place(block, &m, |x, y| x - y),
+                    _ => {}
+                }
+            }
+        };
+        if h <= self.strassen_crossover().max(1) {
+            let pending = (0..7)
+                .map(|k| {
+                    let (x, y) = operands(k);
+                    self.start_multiply(Arc::new(x), Arc::new(y))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            for (k, product) in pending.into_iter().enumerate() {
+                combine(k, product.wait()?);
+            }
+        } else {
+            for k in 0..7 {
+                let (x, y) = operands(k);
+                combine(k, self.strassen(&x, &y)?);
+            }
+        }
+        let [c11, c12, c21, c22] = blocks;
+        Ok(Matrix::from_fn(n, n, |i, j| match (i < h, j < h) {
+            (true, true) => c11.data[i * h + j],
+            (true, false) => c12.data[i * h + j - h],
+            (false, true) => c21.data[(i - h) * h + j],
+            (false, false) => c22.data[(i - h) * h + j - h],
+        }))
+    }
+}
+
+impl<T: Semiring> Matrix<T> {
+    /// Splits a square matrix into four `h x h` blocks, padding with zeros.
+    fn quadrants(&self, h: usize) -> [Matrix<T>; 4] {
+        let block = |r0: usize, c0: usize| {
+            Matrix::from_fn(h, h, |i, j| {
+                self.get(r0 + i, c0 + j).copied().unwrap_or(T::zero())
+            })
+        };
+        [block(0, 0), block(0, h), block(h, 0), block(h, h)]
+    }
+}
+
+fn sum<T: Semiring>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    zip_local(a, b, T::add)
+}
+
+fn diff<T: Copy + Sub<Output = T>>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    zip_local(a, b, |x, y| x - y)
+}
+
+fn zip_in_place<T: Copy>(a: &mut Matrix<T>, b: &Matrix<T>, op: impl Fn(T, T) -> T) {
+    for (x, &y) in a.data.iter_mut().zip(&b.data) {
+        *x = op(*x, y);
+    }
+}
+
+fn zip_local<T: Copy>(a: &Matrix<T>, b: &Matrix<T>, op: impl Fn(T, T) -> T) -> Matrix<T> {
+    Matrix {
+        data: a
+            .data
+            .iter()
+            .zip(&b.data)
+            .map(|(&x, &y)| op(x, y))
+            .collect(),
+        row: a.row,
+        col: a.col,
+        layout: Layout::RowMajor,
+    }
+}
+
+#[test]
+fn test_strassen_matches_multiply() -> anyhow::Result<()> {
+    let executor = MatrixExecutor::builder()
+        .num_threads(3)
+        .granularity(crate::Granularity::Rows(2))
+        .strassen_crossover(2)
+        .build()?;
+    for n in [4, 7, 10] {
+        let a = Matrix::from_fn(n, n, |i, j| (i * 7 + j * 3) as i64 % 11 - 5);
+        let b = Matrix::from_fn(n, n, |i, j| (i * 5 + j * 2) as i64 % 13 - 6);
+        assert_eq!(
+            executor.multiply_strassen(&a, &b)?,
+            executor.multiply(&a, &b)?
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_strassen_falls_back_for_rectangular() -> anyhow::Result<()> {
+    let a = Matrix::from_rows([[1, 2, 3], [4, 5, 6]])?;
+    let b = Matrix::from_rows([[1, 2], [3, 4], [5, 6]])?;
+    assert_eq!(multiply_strassen(&a, &b)?, super::multiply(&a, &b)?);
+    assert!(multiply_strassen(&a, &a).is_err());
+    Ok(())
+}
@@ -0,0 +1,417 @@
+use std::ops::{Index, IndexMut, Range};
+
+use super::{Layout, Matrix};
+use crate::{error::MatrixError, vector::Vector};
+
+/// A borrowed, possibly strided window into a matrix. Element `(i, j)` is
+/// `data[i * row_stride + j * col_stride]`.
+#[derive(Debug)]
+pub struct MatrixView<'a, T> {
+    data: &'a [T],
+    row: usize,
+    col: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+/// A mutable `MatrixView`.
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T> {
+    data: &'a mut [T],
+    row: usize,
+    col: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+/// Anything that can be read as a matrix without copying it. A `Vector` is a
+/// single column.
+pub trait AsMatrixView<T> {
+    fn as_view(&self) -> MatrixView<'_, T>;
+}
+
+impl<T> Clone for MatrixView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MatrixView<'_, T> {}
+
+/// Checks the `rows x cols` window against the parent shape and returns the
+/// offset of its first element.
+fn window(
+    shape: (usize, usize),
+    strides: (usize, usize),
+    rows: &Range<usize>,
+    cols: &Range<usize>,
+) -> usize {
+    if rows.start > rows.end || rows.end > shape.0 || cols.start > cols.end || cols.end > shape.1 {
+        panic!(
+            "submatrix {:?} x {:?} out of bounds for {}x{} matrix",
+            rows, cols, shape.0, shape.1
+        );
+    }
+    if rows.is_empty() || cols.is_empty() {
+        return 0;
+    }
+    rows.start * strides.0 + cols.start * strides.1
+}
+
+impl<'a, T> MatrixView<'a, T> {
+    pub fn rows(&self) -> usize {
+        self.row
+    }
+
+    pub fn cols(&self) -> usize {
+        self.col
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&'a T> {
+        if i < self.row && j < self.col {
+            self.data.get(i * self.row_stride + j * self.col_stride)
+        } else {
+            None
+        }
+    }
+
+    /// Row `i` as a `1 x cols` view.
+    pub fn row(&self, i: usize) -> MatrixView<'a, T> {
+        self.submatrix(i..i + 1, 0..self.col)
+    }
+
+    /// Column `j` as a `rows x 1` view.
+    pub fn col(&self, j: usize) -> MatrixView<'a, T> {
+        self.submatrix(0..self.row, j..j + 1)
+    }
+
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'a, T> {
+        let offset = window(
+            self.shape(),
+            (self.row_stride, self.col_stride),
+            &rows,
+            &cols,
+        );
+        MatrixView {
+            data: self.data.get(offset..).unwrap_or_default(),
+            row: rows.len(),
+            col: cols.len(),
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    /// The transpose, as a view with swapped strides.
+    pub fn transpose(&self) -> MatrixView<'a, T> {
+        MatrixView {
+            data: self.data,
+            row: self.col,
+            col: self.row,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    /// The elements in row-major order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'a T> {
+        let Self {
+            data,
+            col,
+            row_stride,
+            col_stride,
+            ..
+        } = *self;
+        (0..self.row * col)
+            .map(move |idx| &data[(idx / col) * row_stride + (idx % col) * col_stride])
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        self.to_layout(Layout::RowMajor)
+    }
+}
+
+impl<'a, T> MatrixViewMut<'a, T> {
+    pub fn rows(&self) -> usize {
+        self.row
+    }
+
+    pub fn cols(&self) -> usize {
+        self.col
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        self.as_view().get(i, j)
+    }
+
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        if i < self.row && j < self.col {
+            self.data.get_mut(i * self.row_stride + j * self.col_stride)
+        } else {
+            None
+        }
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> MatrixViewMut<'_, T> {
+        let col = self.col;
+        self.submatrix_mut(i..i + 1, 0..col)
+    }
+
+    pub fn col_mut(&mut self, j: usize) -> MatrixViewMut<'_, T> {
+        let row = self.row;
+        self.submatrix_mut(0..row, j..j + 1)
+    }
+
+    pub fn submatrix_mut(
+        &mut self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixViewMut<'_, T> {
+        self.reborrow().into_submatrix(rows, cols)
+    }
+
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for i in 0..self.row {
+            for j in 0..self.col {
+                self[(i, j)] = value.clone();
+            }
+        }
+    }
+
+    /// Overwrites this window with `src`, which must have the same shape.
+    pub fn copy_from(&mut self, src: &impl AsMatrixView<T>) -> Result<(), MatrixError>
+    where
+        T: Clone,
+    {
+        let src = src.as_view();
+        if src.shape() != self.shape() {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.shape(),
+                right: src.shape(),
+            });
+        }
+        for i in 0..self.row {
+            for j in 0..self.col {
+                self[(i, j)] = src[(i, j)].clone();
+            }
+        }
+        Ok(())
+    }
+
+    fn reborrow(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut {
+            data: self.data,
+            row: self.row,
+            col: self.col,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    fn into_submatrix(self, rows: Range<usize>, cols: Range<usize>) -> MatrixViewMut<'a, T> {
+        let offset = window(
+            self.shape(),
+            (self.row_stride, self.col_stride),
+            &rows,
+            &cols,
+        );
+        MatrixViewMut {
+            data: self.data.get_mut(offset..).unwrap_or_default(),
+            row: rows.len(),
+            col: cols.len(),
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+}
+
+impl<T> Matrix<T> {
+    pub fn view(&self) -> MatrixView<'_, T> {
+        let (row_stride, col_stride) = self.strides();
+        MatrixView {
+            data: &self.data,
+            row: self.row,
+            col: self.col,
+            row_stride,
+            col_stride,
+        }
+    }
+
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        let (row_stride, col_stride) = self.strides();
+        MatrixViewMut {
+            data: &mut self.data,
+            row: self.row,
+            col: self.col,
+            row_stride,
+            col_stride,
+        }
+    }
+
+    fn strides(&self) -> (usize, usize) {
+        match self.layout {
+            Layout::RowMajor => (self.col, 1),
+            Layout::ColMajor => (1, self.row),
+        }
+    }
+
+    pub fn row(&self, i: usize) -> MatrixView<'_, T> {
+        self.view().row(i)
+    }
+
+    pub fn col(&self, j: usize) -> MatrixView<'_, T> {
+        self.view().col(j)
+    }
+
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, T> {
+        self.view().submatrix(rows, cols)
+    }
+
+    pub fn submatrix_mut(
+        &mut self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixViewMut<'_, T> {
+        self.view_mut().into_submatrix(rows, cols)
+    }
+}
+
+impl<T, M> AsMatrixView<T> for &M
+where
+    M: AsMatrixView<T> + ?Sized,
+{
+    fn as_view(&self) -> MatrixView<'_, T> {
+        (**self).as_view()
+    }
+}
+
+impl<T> AsMatrixView<T> for Matrix<T> {
+    fn as_view(&self) -> MatrixView<'_, T> {
+        self.view()
+    }
+}
+
+impl<T> AsMatrixView<T> for MatrixView<'_, T> {
+    fn as_view(&self) -> MatrixView<'_, T> {
+        *self
+    }
+}
+
+impl<T> AsMatrixView<T> for MatrixViewMut<'_, T> {
+    fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            data: self.data,
+            row: self.row,
+            col: self.col,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+}
+
+impl<T> AsMatrixView<T> for Vector<T> {
+    fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            data: self,
+            row: self.len(),
+            col: 1,
+            row_stride: 1,
+            col_stride: 1,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        self.get(i, j).unwrap_or_else(|| {
+            panic!(
+                "index ({}, {}) out of bounds for {}x{} view",
+                i, j, self.row, self.col
+            )
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        let (row, col) = self.shape();
+        self.get(i, j).unwrap_or_else(|| {
+            panic!(
+                "index ({}, {}) out of bounds for {}x{} view",
+                i, j, row, col
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for MatrixViewMut<'_, T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        let (row, col) = self.shape();
+        self.get_mut(i, j).unwrap_or_else(|| {
+            panic!(
+                "index ({}, {}) out of bounds for {}x{} view",
+                i, j, row, col
+            )
+        })
+    }
+}
+
+#[test]
+fn test_views_slice_without_copying() -> anyhow::Result<()> {
+    let a = Matrix::from_fn(4, 5, |i, j| (i * 10 + j) as i32);
+    let sub = a.submatrix(1..3, 2..5);
+    assert_eq!(sub.shape(), (2, 3));
+    assert_eq!(
+        sub.to_matrix(),
+        Matrix::from_rows([[12, 13, 14], [22, 23, 24]])?
+    );
+    assert_eq!(sub.col(1).iter().copied().collect::<Vec<_>>(), vec![13, 23]);
+    assert_eq!(sub.transpose()[(2, 1)], 24);
+    assert_eq!(sub.submatrix(1..2, 0..3).row(0)[(0, 2)], 24);
+    assert_eq!(a.submatrix(4..4, 0..5).iter().count(), 0);
+
+    let b = Matrix::from_rows([[1, 0], [0, 1], [1, 1]])?;
+    let product = super::multiply(&sub, &b)?;
+    assert_eq!(product, super::multiply(&sub.to_matrix(), &b)?);
+    let row = a.row(1).submatrix(0..1, 0..4);
+    assert_eq!(
+        crate::dot_product(&row, &a.col(2))?,
+        10 * 2 + 11 * 12 + 12 * 22 + 13 * 32
+    );
+    assert_eq!(
+        crate::dot_product(&Vector::new([1, 2]), &sub.col(0))?,
+        12 + 2 * 22
+    );
+    Ok(())
+}
+
+#[test]
+fn test_view_mut_writes_through() -> anyhow::Result<()> {
+    let mut a = Matrix::<i32>::zeros(3, 3);
+    a.submatrix_mut(0..2, 1..3).fill(7);
+    a.view_mut().col_mut(0).copy_from(&Vector::new([1, 2, 3]))?;
+    assert_eq!(a, Matrix::from_rows([[1, 7, 7], [2, 7, 7], [3, 0, 0]])?);
+    assert!(a
+        .view_mut()
+        .row_mut(2)
+        .copy_from(&Vector::new([1, 2, 3]))
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use std::fmt;
+
+/// The element operations a matrix product needs: `add` must be associative
+/// and commutative with identity `zero`, and `mul` must be associative with
+/// identity `one`, distribute over `add`, and be annihilated by `zero`.
+pub trait Semiring: Copy + Send + Sync + 'static {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn add(self, rhs: Self) -> Self;
+    fn mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_semiring {
+    ($($t:ty),*) => {$(
+        impl Semiring for $t {
+            fn zero() -> Self {
+                0 as $t
+            }
+
+            fn one() -> Self {
+                1 as $t
+            }
+
+            fn add(self, rhs: Self) -> Self {
+                self + rhs
+            }
+
+            fn mul(self, rhs: Self) -> Self {
+                self * rhs
+            }
+        }
+    )*};
+}
+
+impl_semiring!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// How integer products handle overflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arithmetic {
+    /// Fail with `MatrixError::Overflow` naming the output cell.
+    Checked,
+    Saturating,
+    Wrapping,
+}
+
+/// Integer semirings whose `add` and `mul` can overflow. `None` means the
+/// operation overflowed in `Arithmetic::Checked` mode.
+pub trait Integer: Semiring + Eq {
+    fn add_with(self, rhs: Self, mode: Arithmetic) -> Option<Self>;
+    fn mul_with(self, rhs: Self, mode: Arithmetic) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            fn add_with(self, rhs: Self, mode: Arithmetic) -> Option<Self> {
+                match mode {
+                    Arithmetic::Checked => self.checked_add(rhs),
+                    Arithmetic::Saturating => Some(self.saturating_add(rhs)),
+                    Arithmetic::Wrapping => Some(self.wrapping_add(rhs)),
+                }
+            }
+
+            fn mul_with(self, rhs: Self, mode: Arithmetic) -> Option<Self> {
+                match mode {
+                    Arithmetic::Checked => self.checked_mul(rhs),
+                    Arithmetic::Saturating => Some(self.saturating_mul(rhs)),
+                    Arithmetic::Wrapping => Some(self.wrapping_mul(rhs)),
+                }
+            }
+        }
+    )*};
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// The boolean semiring (`or`, `and`); powers of an adjacency matrix give
+/// reachability.
+impl Semiring for bool {
+    fn zero() -> Self {
+        false
+    }
+
+    fn one() -> Self {
+        true
+    }
+
+    fn add(self, rhs: Self) -> Self {
+        self || rhs
+    }
+
+    fn mul(self, rhs: Self) -> Self {
+        self && rhs
+    }
+}
+
+/// The tropical semiring (`min`, `+`) with "infinity" as zero; products of
+/// distance matrices give shortest paths.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MinPlus<T>(pub T);
+
+/// The (`max`, `+`) semiring with "negative infinity" as zero; products give
+/// longest paths.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MaxPlus<T>(pub T);
+
+macro_rules! impl_tropical_float {
+    ($($t:ty),*) => {$(
+        impl Semiring for MinPlus<$t> {
+            fn zero() -> Self {
+                MinPlus(<$t>::INFINITY)
+            }
+
+            fn one() -> Self {
+                MinPlus(0.0)
+            }
+
+            fn add(self, rhs: Self) -> Self {
+                MinPlus(self.0.min(rhs.0))
+            }
+
+            fn mul(self, rhs: Self) -> Self {
+                MinPlus(self.0 + rhs.0)
+            }
+        }
+
+        impl Semiring for MaxPlus<$t> {
+            fn zero() -> Self {
+                MaxPlus(<$t>::NEG_INFINITY)
+            }
+
+            fn one() -> Self {
+                MaxPlus(0.0)
+            }
+
+            fn add(self, rhs: Self) -> Self {
+                MaxPlus(self.0.max(rhs.0))
+            }
+
+            fn mul(self, rhs: Self) -> Self {
+                MaxPlus(self.0 + rhs.0)
+            }
+        }
+    )*};
+}
+
+// integers use MAX / MIN as the infinities, which absorb under `mul`
+macro_rules! impl_tropical_int {
+    ($($t:ty),*) => {$(
+        impl Semiring for MinPlus<$t> {
+            fn zero() -> Self {
+                MinPlus(<$t>::MAX)
+            }
+
+            fn one() -> Self {
+                MinPlus(0)
+            }
+
+            fn add(self, rhs: Self) -> Self {
+                MinPlus(self.0.min(rhs.0))
+            }
+
+            fn mul(self, rhs: Self) -> Self {
+                if self.0 == <$t>::MAX || rhs.0 == <$t>::MAX {
+                    return Self::zero();
+                }
+                MinPlus(self.0.saturating_add(rhs.0))
+            }
+        }
+
+        impl Semiring for MaxPlus<$t> {
+            fn zero() -> Self {
+                MaxPlus(<$t>::MIN)
+            }
+
+            fn one() -> Self {
+                MaxPlus(0)
+            }
+
+            fn add(self, rhs: Self) -> Self {
+                MaxPlus(self.0.max(rhs.0))
+            }
+
+            fn mul(self, rhs: Self) -> Self {
+                if self.0 == <$t>::MIN || rhs.0 == <$t>::MIN {
+                    return Self::zero();
+                }
+                MaxPlus(self.0.saturating_add(rhs.0))
+            }
+        }
+    )*};
+}
+
+impl_tropical_float!(f32, f64);
+impl_tropical_int!(i8, i16, i32, i64, i128, isize);
+
+impl<T: fmt::Display> fmt::Display for MinPlus<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for MaxPlus<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+use crate::matrix::{multiply, Matrix};
+
+#[test]
+fn test_min_plus_shortest_paths() -> anyhow::Result<()> {
+    let inf = MinPlus(f64::INFINITY);
+    let d = Matrix::from_rows([
+        [MinPlus(0.0), MinPlus(4.0), MinPlus(1.0)],
+        [inf, MinPlus(0.0), inf],
+        [inf, MinPlus(2.0), MinPlus(0.0)],
+    ])?;
+    let paths = multiply(&d, &d)?;
+    assert_eq!(paths[(0, 1)], MinPlus(3.0));
+    assert_eq!(paths[(1, 0)], inf);
+    assert_eq!(multiply(&paths, &Matrix::identity(3))?, paths);
+
+    let d = Matrix::from_rows([[MaxPlus(0), MaxPlus(5)], [MaxPlus(i32::MIN), MaxPlus(0)]])?;
+    assert_eq!(multiply(&d, &d)?[(0, 1)], MaxPlus(5));
+    Ok(())
+}
+
+#[test]
+fn test_boolean_transitive_closure() -> anyhow::Result<()> {
+    let adjacency = Matrix::from_rows([
+        [true, true, false],
+        [false, true, true],
+        [false, false, true],
+    ])?;
+    let reach = multiply(&adjacency, &adjacency)?;
+    assert!(reach[(0, 2)]);
+    assert!(!reach[(2, 0)]);
+    Ok(())
+}
@@ -1,29 +1,89 @@
-use std::{
-    ops::{Add, AddAssign, Deref, Mul},
-    process::Output,
+use std::ops::Deref;
+
+use crate::{
+    error::MatrixError,
+    matrix::AsMatrixView,
+    semiring::{Arithmetic, Integer, Semiring},
 };
 
-use anyhow::{Ok, Result};
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Vector<T> {
     data: Vec<T>,
 }
 
-pub fn dot_product<T>(a: Vector<T>, b: Vector<T>) -> Result<T>
+/// Sums the products of corresponding elements, read in row-major order, so
+/// vectors, matrix rows and matrix columns can be mixed freely.
+pub fn dot_product<T, A, B>(a: &A, b: &B) -> Result<T, MatrixError>
+where
+    T: Semiring,
+    A: AsMatrixView<T>,
+    B: AsMatrixView<T>,
+{
+    dot(a.as_view().iter(), b.as_view().iter())
+}
+
+/// Dot product over borrowed elements, so strided matrix columns can be used
+/// without collecting them first.
+pub(crate) fn dot<'a, T>(
+    a: impl ExactSizeIterator<Item = &'a T>,
+    b: impl ExactSizeIterator<Item = &'a T>,
+) -> Result<T, MatrixError>
 where
-    T: Copy + Default + Add<Output = T> + Mul<Output = T> + AddAssign,
+    T: Semiring + 'a,
 {
     if a.len() != b.len() {
-        return Err(anyhow::anyhow!("Vector length mismatch"));
+        return Err(MatrixError::LengthMismatch {
+            left: a.len(),
+            right: b.len(),
+        });
     }
 
-    let mut sum = T::default();
-    for i in 0..a.len() {
-        sum += a[i] * b[i];
+    let mut sum = T::zero();
+    for (x, y) in a.zip(b) {
+        sum = sum.add(x.mul(*y));
     }
 
     Ok(sum)
 }
 
+/// `dot_product` for integers with an explicit overflow mode. An overflow
+/// is reported at cell `(0, 0)`.
+pub fn dot_product_with<T, A, B>(a: &A, b: &B, mode: Arithmetic) -> Result<T, MatrixError>
+where
+    T: Integer,
+    A: AsMatrixView<T>,
+    B: AsMatrixView<T>,
+{
+    dot_with(a.as_view().iter(), b.as_view().iter(), mode)?
+        .ok_or(MatrixError::Overflow { row: 0, col: 0 })
+}
+
+/// `dot` in the given overflow mode; `Ok(None)` if it overflowed.
+pub(crate) fn dot_with<'a, T: Integer>(
+    a: impl ExactSizeIterator<Item = &'a T>,
+    b: impl ExactSizeIterator<Item = &'a T>,
+    mode: Arithmetic,
+) -> Result<Option<T>, MatrixError> {
+    if a.len() != b.len() {
+        return Err(MatrixError::LengthMismatch {
+            left: a.len(),
+            right: b.len(),
+        });
+    }
+
+    let mut sum = T::zero();
+    for (x, y) in a.zip(b) {
+        match x.mul_with(*y, mode).and_then(|p| sum.add_with(p, mode)) {
+            Some(s) => sum = s,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(sum))
+}
+
 impl<T> Deref for Vector<T> {
     type Target = Vec<T>;
 
@@ -37,3 +97,32 @@ impl<T> Vector<T> {
         Self { data: data.into() }
     }
 }
+
+#[test]
+fn test_dot_product_length_mismatch() {
+    let a = Vector::new([1, 2, 3]);
+    let b = Vector::new([1, 2]);
+    assert!(matches!(
+        dot_product(&a, &b),
+        Err(MatrixError::LengthMismatch { left: 3, right: 2 })
+    ));
+    assert_eq!(dot_product(&a, &a).ok(), Some(14));
+}
+
+#[test]
+fn test_dot_product_overflow_modes() {
+    let a = Vector::new([i32::MAX, 1]);
+    let b = Vector::new([1, 1]);
+    assert!(matches!(
+        dot_product_with(&a, &b, Arithmetic::Checked),
+        Err(MatrixError::Overflow { row: 0, col: 0 })
+    ));
+    assert_eq!(
+        dot_product_with(&a, &b, Arithmetic::Saturating).ok(),
+        Some(i32::MAX)
+    );
+    assert_eq!(
+        dot_product_with(&a, &b, Arithmetic::Wrapping).ok(),
+        Some(i32::MIN)
+    );
+}